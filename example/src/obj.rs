@@ -6,6 +6,9 @@ pub fn decode(s: String) -> std::io::Result<TriMesh> {
     let mut normals: Vec<Vec3> = Vec::new();
     let mut uvs: Vec<Vec2> = Vec::new();
     let mut triangles: Vec<Triangle> = Vec::new();
+    // materials are numbered in the order they're first used
+    let mut materials: Vec<&str> = Vec::new();
+    let mut material = 0;
 
     for line in s.lines() {
         let mut args = line.split(" ").skip(1);
@@ -35,6 +38,15 @@ pub fn decode(s: String) -> std::io::Result<TriMesh> {
                 y.parse().unwrap(),
                 z.parse().unwrap(),
             ));
+        } else if line.starts_with("usemtl ") {
+            let name = args.next().unwrap();
+            material = match materials.iter().position(|&m| m == name) {
+                Some(i) => i,
+                None => {
+                    materials.push(name);
+                    materials.len() - 1
+                }
+            } as u32;
         } else if line.starts_with("f ") {
             let mut a = args.next().unwrap().split("/");
             let mut b = args.next().unwrap().split("/");
@@ -48,19 +60,22 @@ pub fn decode(s: String) -> std::io::Result<TriMesh> {
             let v2 = c.next().unwrap();
             let uv2 = c.next().unwrap();
 
-            triangles.push(Triangle::new(
-                [
-                    vertices[v0.parse::<usize>().unwrap() - 1],
-                    vertices[v1.parse::<usize>().unwrap() - 1],
-                    vertices[v2.parse::<usize>().unwrap() - 1],
-                ],
-                [
-                    uvs[uv0.parse::<usize>().unwrap() - 1],
-                    uvs[uv1.parse::<usize>().unwrap() - 1],
-                    uvs[uv2.parse::<usize>().unwrap() - 1],
-                ],
-                normals[n0.parse::<usize>().unwrap() - 1],
-            ))
+            triangles.push(Triangle {
+                material,
+                ..Triangle::new(
+                    [
+                        vertices[v0.parse::<usize>().unwrap() - 1],
+                        vertices[v1.parse::<usize>().unwrap() - 1],
+                        vertices[v2.parse::<usize>().unwrap() - 1],
+                    ],
+                    [
+                        uvs[uv0.parse::<usize>().unwrap() - 1],
+                        uvs[uv1.parse::<usize>().unwrap() - 1],
+                        uvs[uv2.parse::<usize>().unwrap() - 1],
+                    ],
+                    normals[n0.parse::<usize>().unwrap() - 1],
+                )
+            })
         }
     }
 
@@ -0,0 +1,278 @@
+use std::{any::Any, fmt::Debug, ops::Range};
+
+use glam::{Vec2, Vec3, Vec4};
+
+/// A value that can be blended between vertices
+pub trait Interpolate: Clone + Default + Debug + Send + Sync + 'static {
+    /// returns the value `t` of the way from `self` to `other`
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for Vec2 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vec2::lerp(*self, *other, t)
+    }
+}
+
+impl Interpolate for Vec3 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vec3::lerp(*self, *other, t)
+    }
+}
+
+impl Interpolate for Vec4 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Vec4::lerp(*self, *other, t)
+    }
+}
+
+/// Where a new value comes from
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Source {
+    /// the default value of the channel
+    Default,
+    Copy(usize),
+    /// `t` of the way from the first value to the second
+    Lerp(usize, usize, f32),
+    /// the average of the values
+    Average(Vec<usize>),
+}
+
+impl Source {
+    /// returns this source with every index moved by `by`
+    pub(crate) fn offset(&self, by: usize) -> Source {
+        match self {
+            Self::Default => Self::Default,
+            Self::Copy(i) => Self::Copy(i + by),
+            Self::Lerp(a, b, t) => Self::Lerp(a + by, b + by, *t),
+            Self::Average(values) => Self::Average(values.iter().map(|i| i + by).collect()),
+        }
+    }
+}
+
+/// The values of a single channel,
+/// lets `Attributes` hold any `Interpolate` type
+trait Channel: Debug + Send + Sync {
+    fn len(&self) -> usize;
+    /// returns a channel with a value for every source
+    fn gather(&self, sources: &[Source]) -> Box<dyn Channel>;
+    /// adds default values or removes values until there are `len`
+    fn resize(&mut self, len: usize);
+    fn remove(&mut self, range: Range<usize>);
+    fn swap(&mut self, a: usize, b: usize);
+    /// adds the values of `other` to the end
+    /// returns false if `other` holds a different type
+    fn append(&mut self, other: &dyn Channel) -> bool;
+    fn boxed_clone(&self) -> Box<dyn Channel>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Interpolate> Channel for Vec<T> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn gather(&self, sources: &[Source]) -> Box<dyn Channel> {
+        let values: Vec<T> = sources
+            .iter()
+            .map(|source| match source {
+                Source::Default => T::default(),
+                Source::Copy(i) => self[*i].clone(),
+                Source::Lerp(a, b, t) => self[*a].lerp(&self[*b], *t),
+                Source::Average(values) => {
+                    let mut out = T::default();
+                    for (n, &i) in values.iter().enumerate() {
+                        out = out.lerp(&self[i], 1.0 / (n + 1) as f32);
+                    }
+
+                    out
+                }
+            })
+            .collect();
+
+        Box::new(values)
+    }
+
+    fn resize(&mut self, len: usize) {
+        Vec::resize(self, len, T::default());
+    }
+
+    fn remove(&mut self, range: Range<usize>) {
+        self.drain(range);
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.as_mut_slice().swap(a, b);
+    }
+
+    fn append(&mut self, other: &dyn Channel) -> bool {
+        match other.as_any().downcast_ref::<Vec<T>>() {
+            Some(other) => {
+                self.extend_from_slice(other);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Channel> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Named channels of custom vertex values,
+/// like colors, second uv sets or bone weights
+///
+/// every channel has the same amount of values,
+/// meshes keep them in step with their vertices  
+/// modifiers interpolate every channel when they create new vertices
+#[derive(Debug, Default)]
+pub struct Attributes {
+    channels: Vec<(String, Box<dyn Channel>)>,
+}
+
+impl Attributes {
+    /// Adds a channel
+    /// replacing the channel with the same name
+    ///
+    /// ### Panic
+    /// panics if `values` has a different length than the other channels
+    pub fn insert<T: Interpolate>(&mut self, name: impl Into<String>, values: Vec<T>) {
+        let name = name.into();
+        self.channels.retain(|(n, _)| n != &name);
+        assert!(
+            self.channels.is_empty() || values.len() == self.len(),
+            "attribute `{name}` has {} values instead of {}",
+            values.len(),
+            self.len()
+        );
+
+        self.channels.push((name, Box::new(values)));
+    }
+
+    /// returns the values of a channel
+    /// returns None if there's no channel named `name` holding `T`
+    pub fn get<T: Interpolate>(&self, name: &str) -> Option<&[T]> {
+        let (_, channel) = self.channels.iter().find(|(n, _)| n == name)?;
+        channel.as_any().downcast_ref::<Vec<T>>().map(Vec::as_slice)
+    }
+
+    /// returns the values of a channel
+    /// returns None if there's no channel named `name` holding `T`
+    pub fn get_mut<T: Interpolate>(&mut self, name: &str) -> Option<&mut [T]> {
+        let (_, channel) = self.channels.iter_mut().find(|(n, _)| n == name)?;
+        channel
+            .as_any_mut()
+            .downcast_mut::<Vec<T>>()
+            .map(Vec::as_mut_slice)
+    }
+
+    /// Removes a channel
+    /// returns false if there's no channel named `name`
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.channels.len();
+        self.channels.retain(|(n, _)| n != name);
+        self.channels.len() != len
+    }
+
+    /// returns the names of the channels in the order they were added
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.channels.iter().map(|(n, _)| n.as_str())
+    }
+
+    /// returns true if there are no channels
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    /// returns the amount of values in every channel
+    pub fn len(&self) -> usize {
+        self.channels.first().map_or(0, |(_, c)| c.len())
+    }
+
+    /// returns channels with a value for every source
+    pub(crate) fn gather(&self, sources: &[Source]) -> Self {
+        let channels = self
+            .channels
+            .iter()
+            .map(|(n, c)| (n.clone(), c.gather(sources)))
+            .collect();
+
+        Self { channels }
+    }
+
+    /// Adds default values or removes values until there are `len`
+    pub(crate) fn resize(&mut self, len: usize) {
+        for (_, channel) in &mut self.channels {
+            channel.resize(len);
+        }
+    }
+
+    /// Removes the values in `range` from every channel
+    pub(crate) fn remove_range(&mut self, range: Range<usize>) {
+        for (_, channel) in &mut self.channels {
+            channel.remove(range.clone());
+        }
+    }
+
+    /// Adds the `other_len` values of `other` after the `len` values of every channel
+    ///
+    /// channels only one side has get default values for the other side,
+    /// a channel of `other` with the name of a channel holding another type is dropped
+    pub(crate) fn append(&mut self, len: usize, other: &Self, other_len: usize) {
+        for (name, channel) in &mut self.channels {
+            let appended = other
+                .channels
+                .iter()
+                .find(|(n, _)| n == name)
+                .is_some_and(|(_, c)| channel.append(c.as_ref()));
+            if !appended {
+                channel.resize(len + other_len);
+            }
+        }
+
+        let sources: Vec<Source> = (0..len)
+            .map(|_| Source::Default)
+            .chain((0..other_len).map(Source::Copy))
+            .collect();
+        for (name, channel) in &other.channels {
+            if self.channels.iter().all(|(n, _)| n != name) {
+                self.channels.push((name.clone(), channel.gather(&sources)));
+            }
+        }
+    }
+
+    /// Swaps two values in every channel
+    pub(crate) fn swap(&mut self, a: usize, b: usize) {
+        for (_, channel) in &mut self.channels {
+            channel.swap(a, b);
+        }
+    }
+}
+
+impl Clone for Attributes {
+    fn clone(&self) -> Self {
+        let channels = self
+            .channels
+            .iter()
+            .map(|(n, c)| (n.clone(), c.boxed_clone()))
+            .collect();
+
+        Self { channels }
+    }
+}
@@ -0,0 +1,141 @@
+use glam::Vec3;
+
+use super::{Bvh, Node};
+
+/// the amount of bins used to estimate the surface area heuristic
+const BINS: usize = 12;
+/// the most triangles a leaf holds before it's split
+const MAX_LEAF: usize = 4;
+
+#[derive(Clone, Copy)]
+struct Bounds {
+    min: Vec3,
+    max: Vec3,
+}
+
+impl Bounds {
+    const EMPTY: Self = Self {
+        min: Vec3::splat(f32::INFINITY),
+        max: Vec3::splat(f32::NEG_INFINITY),
+    };
+
+    fn grow(&mut self, min: Vec3, max: Vec3) {
+        self.min = self.min.min(min);
+        self.max = self.max.max(max);
+    }
+
+    fn area(&self) -> f32 {
+        let e = (self.max - self.min).max(Vec3::ZERO);
+        e.x * e.y + e.y * e.z + e.z * e.x
+    }
+}
+
+impl Bvh {
+    /// Builds the tree for `self.triangles` using a binned SAH split
+    pub(super) fn build(&mut self) {
+        let bounds: Vec<[Vec3; 2]> = self.triangles.iter().map(|t| [t.min(), t.max()]).collect();
+        let centroids: Vec<Vec3> = bounds.iter().map(|b| (b[0] + b[1]) * 0.5).collect();
+
+        self.indices = (0..self.triangles.len()).collect();
+        self.nodes.clear();
+        self.nodes.push(Node::default());
+        if !self.triangles.is_empty() {
+            self.split(0, 0, self.indices.len(), &bounds, &centroids);
+        }
+    }
+
+    fn split(
+        &mut self,
+        node: usize,
+        start: usize,
+        end: usize,
+        bounds: &[[Vec3; 2]],
+        centroids: &[Vec3],
+    ) {
+        let mut node_bounds = Bounds::EMPTY;
+        let mut centroid_bounds = Bounds::EMPTY;
+        for &i in &self.indices[start..end] {
+            node_bounds.grow(bounds[i][0], bounds[i][1]);
+            centroid_bounds.grow(centroids[i], centroids[i]);
+        }
+
+        self.nodes[node] = Node {
+            min: node_bounds.min,
+            max: node_bounds.max,
+            first: start,
+            count: end - start,
+        };
+
+        let count = end - start;
+        if count <= MAX_LEAF {
+            return;
+        }
+
+        let extent = centroid_bounds.max - centroid_bounds.min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        if extent[axis] <= 0.0 {
+            return;
+        }
+
+        // Bin the centroids along the longest axis
+        let bin_of = |c: Vec3| {
+            let b = (c[axis] - centroid_bounds.min[axis]) / extent[axis] * BINS as f32;
+            (b as usize).min(BINS - 1)
+        };
+
+        let mut bins = [(Bounds::EMPTY, 0usize); BINS];
+        for &i in &self.indices[start..end] {
+            let bin = &mut bins[bin_of(centroids[i])];
+            bin.0.grow(bounds[i][0], bounds[i][1]);
+            bin.1 += 1;
+        }
+
+        // Find the cheapest plane between two bins
+        let mut right_cost = [0.0; BINS];
+        let mut acc = (Bounds::EMPTY, 0);
+        for b in (1..BINS).rev() {
+            acc.0.grow(bins[b].0.min, bins[b].0.max);
+            acc.1 += bins[b].1;
+            right_cost[b] = acc.0.area() * acc.1 as f32;
+        }
+
+        let mut best = (f32::INFINITY, 0);
+        let mut acc = (Bounds::EMPTY, 0);
+        for b in 0..BINS - 1 {
+            acc.0.grow(bins[b].0.min, bins[b].0.max);
+            acc.1 += bins[b].1;
+            let cost = acc.0.area() * acc.1 as f32 + right_cost[b + 1];
+            if acc.1 > 0 && acc.1 < count && cost < best.0 {
+                best = (cost, b);
+            }
+        }
+
+        // Stop when splitting costs more than intersecting every triangle
+        if best.0 >= node_bounds.area() * count as f32 {
+            return;
+        }
+
+        let mut mid = start;
+        for i in start..end {
+            if bin_of(centroids[self.indices[i]]) <= best.1 {
+                self.indices.swap(i, mid);
+                mid += 1;
+            }
+        }
+
+        let left = self.nodes.len();
+        self.nodes.push(Node::default());
+        self.nodes.push(Node::default());
+        self.nodes[node].first = left;
+        self.nodes[node].count = 0;
+
+        self.split(left, start, mid, bounds, centroids);
+        self.split(left + 1, mid, end, bounds, centroids);
+    }
+}
@@ -0,0 +1,213 @@
+mod build;
+
+use glam::Vec3;
+
+use crate::{hit::RayHit, meshes::TriMesh, traits::Raycast, triangle::Triangle, Ray, Segment};
+
+/// A bounding volume hierarchy over the triangles of a `TriMesh`
+///
+/// answers ray, segment and bounding box queries
+/// without testing every triangle
+#[derive(Clone, Debug, Default)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    indices: Vec<usize>,
+    triangles: Vec<Triangle>,
+}
+
+/// A leaf when `count > 0`, holding `indices[first..first + count]`  
+/// otherwise it's children are `first` and `first + 1`
+#[derive(Clone, Copy, Debug, Default)]
+struct Node {
+    min: Vec3,
+    max: Vec3,
+    first: usize,
+    count: usize,
+}
+
+impl Bvh {
+    /// Builds a `Bvh` from the triangles in `mesh`
+    pub fn new(mesh: &TriMesh) -> Self {
+        let mut bvh = Self {
+            nodes: Vec::new(),
+            indices: Vec::new(),
+            triangles: mesh.triangles.clone(),
+        };
+
+        bvh.build();
+        bvh
+    }
+
+    /// returns the index of the closest triangle hit by `ray`
+    /// and where it was hit
+    pub fn closest_hit(&self, ray: &Ray) -> Option<(usize, RayHit)> {
+        self.cast(ray, 0.0, f32::INFINITY, false)
+    }
+
+    /// returns whether `ray` hits any triangle
+    pub fn any_hit(&self, ray: &Ray) -> bool {
+        self.cast(ray, 0.0, f32::INFINITY, true).is_some()
+    }
+
+    /// returns the index of the triangle hit closest to `segment[0]`
+    /// and where it was hit
+    ///
+    /// the hit distance is relative to the segment's length,
+    /// going from 0.0 at `segment[0]` to 1.0 at `segment[1]`
+    pub fn segment_hit(&self, segment: &Segment) -> Option<(usize, RayHit)> {
+        let ray = (segment[0], segment[1] - segment[0]);
+        self.cast(&ray, 0.0, 1.0, false)
+    }
+
+    /// returns the index of every triangle
+    /// whose bounding box overlaps the box from `min` to `max`
+    pub fn overlaps(&self, min: Vec3, max: Vec3) -> Vec<usize> {
+        let mut out = Vec::new();
+        if self.triangles.is_empty() {
+            return out;
+        }
+
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if node.min.cmpgt(max).any() || node.max.cmplt(min).any() {
+                continue;
+            }
+
+            if node.count == 0 {
+                stack.push(node.first);
+                stack.push(node.first + 1);
+                continue;
+            }
+
+            for &i in &self.indices[node.first..node.first + node.count] {
+                let t = &self.triangles[i];
+                if !(t.min().cmpgt(max).any() || t.max().cmplt(min).any()) {
+                    out.push(i);
+                }
+            }
+        }
+
+        out.sort_unstable();
+        out
+    }
+
+    /// returns the index of the triangle closest to `point`
+    /// and the closest point on it
+    pub fn closest_point(&self, point: Vec3) -> Option<(usize, Vec3)> {
+        if self.triangles.is_empty() {
+            return None;
+        }
+
+        let mut closest = None;
+        let mut best = f32::INFINITY;
+
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if node.count > 0 {
+                for &i in &self.indices[node.first..node.first + node.count] {
+                    let p = self.triangles[i].closest_point(point);
+                    let distance = p.distance_squared(point);
+                    if distance < best {
+                        best = distance;
+                        closest = Some((i, p));
+                    }
+                }
+
+                continue;
+            }
+
+            let a = self.nodes[node.first].distance_squared(point);
+            let b = self.nodes[node.first + 1].distance_squared(point);
+            let (near, far) = if a <= b {
+                ((node.first, a), (node.first + 1, b))
+            } else {
+                ((node.first + 1, b), (node.first, a))
+            };
+
+            for (child, distance) in [far, near] {
+                if distance < best {
+                    stack.push(child);
+                }
+            }
+        }
+
+        closest
+    }
+
+    /// Traverses the tree nearest child first,
+    /// skipping nodes further away than the closest hit
+    fn cast(&self, ray: &Ray, t_min: f32, t_max: f32, any: bool) -> Option<(usize, RayHit)> {
+        if self.triangles.is_empty() {
+            return None;
+        }
+
+        let inv = ray.1.recip();
+        let mut closest = None;
+        let mut t_max = t_max;
+
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if node.count > 0 {
+                for &i in &self.indices[node.first..node.first + node.count] {
+                    let hit = match self.triangles[i].raycast_range(ray, t_min, t_max) {
+                        Some(hit) => hit,
+                        None => continue,
+                    };
+
+                    t_max = hit.distance;
+                    closest = Some((i, hit));
+
+                    if any {
+                        return closest;
+                    }
+                }
+
+                continue;
+            }
+
+            let a = self.nodes[node.first].slab(ray.0, inv, t_min, t_max);
+            let b = self.nodes[node.first + 1].slab(ray.0, inv, t_min, t_max);
+            match (a, b) {
+                (Some(a), Some(b)) if a <= b => stack.extend([node.first + 1, node.first]),
+                (Some(_), Some(_)) => stack.extend([node.first, node.first + 1]),
+                (Some(_), None) => stack.push(node.first),
+                (None, Some(_)) => stack.push(node.first + 1),
+                (None, None) => {}
+            }
+        }
+
+        closest
+    }
+}
+
+impl Node {
+    /// returns the squared distance from `point` to this node's box
+    fn distance_squared(&self, point: Vec3) -> f32 {
+        point.clamp(self.min, self.max).distance_squared(point)
+    }
+
+    /// returns the distance where a ray enters this node
+    fn slab(&self, origin: Vec3, inv: Vec3, t_min: f32, t_max: f32) -> Option<f32> {
+        let t0 = (self.min - origin) * inv;
+        let t1 = (self.max - origin) * inv;
+        let near = t0.min(t1).max_element().max(t_min);
+        let far = t0.max(t1).min_element().min(t_max);
+
+        if near <= far {
+            Some(near)
+        } else {
+            None
+        }
+    }
+}
+
+impl Raycast for Bvh {
+    /// get the hit record of the closest triangle hit by a ray  
+    /// returns None if there's no intersection in range
+    fn raycast_range(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<RayHit> {
+        self.cast(ray, t_min, t_max, false).map(|(_, hit)| hit)
+    }
+}
@@ -0,0 +1,98 @@
+mod node;
+
+use crate::meshes::{PolyMesh, TriMesh};
+
+use node::{Face, Node};
+
+impl PolyMesh {
+    /// returns the space inside either this mesh or `other`
+    ///
+    /// both meshes need to be closed with outward facing polygons  
+    /// polygons keep their uvs, normals and materials,
+    /// split polygons interpolate them  
+    /// attributes are dropped, the result has no attribute channels
+    pub fn union(&self, other: &PolyMesh) -> PolyMesh {
+        // https://github.com/evanw/csg.js
+        let (mut a, mut b) = (tree(self), tree(other));
+        a.clip_to(&b);
+        b.clip_to(&a);
+        b.invert();
+        b.clip_to(&a);
+        b.invert();
+        a.build(b.all_faces());
+        mesh(&a)
+    }
+
+    /// returns the space inside this mesh but not inside `other`
+    ///
+    /// both meshes need to be closed with outward facing polygons  
+    /// attributes are dropped, see `PolyMesh::union`
+    pub fn difference(&self, other: &PolyMesh) -> PolyMesh {
+        let (mut a, mut b) = (tree(self), tree(other));
+        a.invert();
+        a.clip_to(&b);
+        b.clip_to(&a);
+        b.invert();
+        b.clip_to(&a);
+        b.invert();
+        a.build(b.all_faces());
+        a.invert();
+        mesh(&a)
+    }
+
+    /// returns the space inside both this mesh and `other`
+    ///
+    /// both meshes need to be closed with outward facing polygons  
+    /// attributes are dropped, see `PolyMesh::union`
+    pub fn intersection(&self, other: &PolyMesh) -> PolyMesh {
+        let (mut a, mut b) = (tree(self), tree(other));
+        a.invert();
+        b.clip_to(&a);
+        b.invert();
+        a.clip_to(&b);
+        b.clip_to(&a);
+        a.build(b.all_faces());
+        a.invert();
+        mesh(&a)
+    }
+}
+
+impl TriMesh {
+    /// returns the space inside either this mesh or `other`,
+    /// see `PolyMesh::union`
+    pub fn union(&self, other: &TriMesh) -> TriMesh {
+        PolyMesh::from(self.clone())
+            .union(&other.clone().into())
+            .into()
+    }
+
+    /// returns the space inside this mesh but not inside `other`,
+    /// see `PolyMesh::difference`
+    pub fn difference(&self, other: &TriMesh) -> TriMesh {
+        PolyMesh::from(self.clone())
+            .difference(&other.clone().into())
+            .into()
+    }
+
+    /// returns the space inside both this mesh and `other`,
+    /// see `PolyMesh::intersection`
+    pub fn intersection(&self, other: &TriMesh) -> TriMesh {
+        PolyMesh::from(self.clone())
+            .intersection(&other.clone().into())
+            .into()
+    }
+}
+
+fn tree(mesh: &PolyMesh) -> Node {
+    Node::new(
+        mesh.polygons
+            .iter()
+            .cloned()
+            .filter_map(Face::new)
+            .collect(),
+    )
+}
+
+fn mesh(node: &Node) -> PolyMesh {
+    PolyMesh::new(node.all_faces().into_iter().map(|f| f.polygon).collect())
+}
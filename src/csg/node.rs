@@ -0,0 +1,248 @@
+use glam::Vec3;
+
+use crate::{plane::Plane, polygon::Polygon, vertex::Vertex};
+
+/// points closer than this to a plane are on the plane
+const EPSILON: f32 = 1e-5;
+
+const COPLANAR: u8 = 0;
+const FRONT: u8 = 1;
+const BACK: u8 = 2;
+const SPANNING: u8 = 3;
+
+/// A convex polygon and the plane it lies on
+#[derive(Clone, Debug)]
+pub(super) struct Face {
+    pub polygon: Polygon,
+    plane: Plane,
+}
+
+impl Face {
+    /// returns None if the polygon has no area
+    pub fn new(polygon: Polygon) -> Option<Self> {
+        // Newell's method
+        let mut normal = Vec3::ZERO;
+        for i in 0..polygon.len() {
+            let a = polygon[i].point;
+            let b = polygon[(i + 1) % polygon.len()].point;
+            normal += Vec3::new(
+                (a.y - b.y) * (a.z + b.z),
+                (a.z - b.z) * (a.x + b.x),
+                (a.x - b.x) * (a.y + b.y),
+            );
+        }
+
+        let normal = normal.try_normalize()?;
+        let plane = Plane::new(polygon.center(), normal);
+        Some(Self { polygon, plane })
+    }
+
+    fn flip(&mut self) {
+        self.polygon = self.polygon.flip();
+        self.plane.normal = -self.plane.normal;
+    }
+}
+
+/// A node in a BSP tree
+#[derive(Clone, Debug, Default)]
+pub(super) struct Node {
+    plane: Option<Plane>,
+    front: Option<Box<Node>>,
+    back: Option<Box<Node>>,
+    faces: Vec<Face>,
+}
+
+impl Node {
+    pub fn new(faces: Vec<Face>) -> Self {
+        let mut node = Self::default();
+        node.build(faces);
+        node
+    }
+
+    /// Turns solid space into empty space and empty space into solid space
+    pub fn invert(&mut self) {
+        for face in &mut self.faces {
+            face.flip();
+        }
+
+        if let Some(plane) = &mut self.plane {
+            plane.normal = -plane.normal;
+        }
+
+        if let Some(front) = &mut self.front {
+            front.invert();
+        }
+
+        if let Some(back) = &mut self.back {
+            back.invert();
+        }
+
+        std::mem::swap(&mut self.front, &mut self.back);
+    }
+
+    /// Removes the parts of `faces` inside this tree
+    fn clip_faces(&self, faces: Vec<Face>) -> Vec<Face> {
+        let plane = match &self.plane {
+            Some(plane) => plane,
+            None => return faces,
+        };
+
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        for face in faces {
+            match split(plane, face) {
+                Split::CoplanarFront(face) | Split::Front(face) => front.push(face),
+                Split::CoplanarBack(face) | Split::Back(face) => back.push(face),
+                Split::Spanning(f, b) => {
+                    front.extend(f);
+                    back.extend(b);
+                }
+            }
+        }
+
+        let mut front = match &self.front {
+            Some(node) => node.clip_faces(front),
+            None => front,
+        };
+
+        if let Some(node) = &self.back {
+            front.append(&mut node.clip_faces(back));
+        }
+
+        front
+    }
+
+    /// Removes the parts of this tree's faces inside `other`
+    pub fn clip_to(&mut self, other: &Node) {
+        self.faces = other.clip_faces(std::mem::take(&mut self.faces));
+        if let Some(front) = &mut self.front {
+            front.clip_to(other);
+        }
+
+        if let Some(back) = &mut self.back {
+            back.clip_to(other);
+        }
+    }
+
+    /// returns every face in this tree
+    pub fn all_faces(&self) -> Vec<Face> {
+        let mut out = self.faces.clone();
+        if let Some(front) = &self.front {
+            out.append(&mut front.all_faces());
+        }
+
+        if let Some(back) = &self.back {
+            out.append(&mut back.all_faces());
+        }
+
+        out
+    }
+
+    /// Adds `faces` to this tree
+    /// splitting them with the planes they cross
+    pub fn build(&mut self, faces: Vec<Face>) {
+        if faces.is_empty() {
+            return;
+        }
+
+        let plane = *self.plane.get_or_insert(faces[0].plane);
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        for face in faces {
+            match split(&plane, face) {
+                Split::CoplanarFront(face) | Split::CoplanarBack(face) => self.faces.push(face),
+                Split::Front(face) => front.push(face),
+                Split::Back(face) => back.push(face),
+                Split::Spanning(f, b) => {
+                    front.extend(f);
+                    back.extend(b);
+                }
+            }
+        }
+
+        if !front.is_empty() {
+            self.front.get_or_insert_with(Default::default).build(front);
+        }
+
+        if !back.is_empty() {
+            self.back.get_or_insert_with(Default::default).build(back);
+        }
+    }
+}
+
+/// Where a face is relative to a plane
+enum Split {
+    CoplanarFront(Face),
+    CoplanarBack(Face),
+    Front(Face),
+    Back(Face),
+    Spanning(Option<Face>, Option<Face>),
+}
+
+/// Splits `face` by `plane`
+fn split(plane: &Plane, face: Face) -> Split {
+    let polygon = &face.polygon;
+    let types: Vec<u8> = polygon
+        .vertices
+        .iter()
+        .map(|v| {
+            let t = plane.normal.dot(v.point - plane.point);
+            if t < -EPSILON {
+                BACK
+            } else if t > EPSILON {
+                FRONT
+            } else {
+                COPLANAR
+            }
+        })
+        .collect();
+
+    match types.iter().fold(COPLANAR, |a, b| a | b) {
+        COPLANAR if plane.normal.dot(face.plane.normal) > 0.0 => Split::CoplanarFront(face),
+        COPLANAR => Split::CoplanarBack(face),
+        FRONT => Split::Front(face),
+        BACK => Split::Back(face),
+        _ => {
+            let mut f = Vec::new();
+            let mut b = Vec::new();
+            for i in 0..polygon.len() {
+                let j = (i + 1) % polygon.len();
+                let (ti, tj) = (types[i], types[j]);
+                let (vi, vj) = (polygon[i], polygon[j]);
+
+                if ti != BACK {
+                    f.push(vi);
+                }
+
+                if ti != FRONT {
+                    b.push(vi);
+                }
+
+                if ti | tj == SPANNING {
+                    let vector = vj.point - vi.point;
+                    let t = plane.normal.dot(plane.point - vi.point) / plane.normal.dot(vector);
+                    let v = Vertex::new(
+                        vi.point + vector * t,
+                        vi.uv.lerp(vj.uv, t),
+                        vi.normal.lerp(vj.normal, t),
+                    );
+
+                    f.push(v);
+                    b.push(v);
+                }
+            }
+
+            let part = |vertices: Vec<Vertex>| {
+                (vertices.len() >= 3).then_some(Face {
+                    polygon: Polygon {
+                        vertices,
+                        material: polygon.material,
+                    },
+                    plane: face.plane,
+                })
+            };
+
+            Split::Spanning(part(f), part(b))
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use glam::{Vec2, Vec3};
+
+/// Where and how a ray hit a primitive
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// the point that was hit
+    pub point: Vec3,
+    /// the hit point is at `ray.0 + ray.1 * distance`
+    pub distance: f32,
+    /// the weights of the hit triangle's three points at the hit point  
+    /// polygons use their fan triangle `[0, i, i + 1]` that was hit,
+    /// planes use ZERO
+    pub barycentric: Vec3,
+    /// whether the ray hit the side `normal` points towards
+    pub front_face: bool,
+    /// the interpolated uv at the hit point, planes use ZERO
+    pub uv: Vec2,
+    /// the interpolated normal at the hit point
+    pub normal: Vec3,
+}
@@ -0,0 +1,165 @@
+use glam::Vec3;
+
+use crate::{meshes::TriMesh, plane::Plane};
+
+use super::quickhull;
+
+/// Options for `convex_decomposition`
+#[derive(Clone, Copy, Debug)]
+pub struct DecomposeOptions {
+    /// pieces are split until the volume between them and their hull
+    /// is below this fraction of the whole mesh's hull volume
+    pub concavity: f32,
+    /// the most pieces to split the mesh into
+    pub max_pieces: usize,
+    /// the most vertices a hull can have,
+    /// pieces with more are split further or get a looser hull
+    pub max_hull_vertices: usize,
+    /// how many split planes are tried along each axis
+    pub resolution: usize,
+}
+
+impl Default for DecomposeOptions {
+    fn default() -> Self {
+        Self {
+            concavity: 0.01,
+            max_pieces: 16,
+            max_hull_vertices: 64,
+            resolution: 8,
+        }
+    }
+}
+
+/// A part of the mesh being decomposed
+struct Piece {
+    mesh: TriMesh,
+    /// the volume between the piece and it's hull
+    concavity: f32,
+    /// the amount of vertices on the piece's hull
+    vertices: usize,
+}
+
+impl Piece {
+    fn new(mesh: TriMesh) -> Option<Self> {
+        let hull = mesh.convex_hull()?;
+        Some(Self {
+            concavity: (hull.volume() - mesh.volume()).max(0.0),
+            // a closed triangulated hull has 2 * vertices - 4 triangles
+            vertices: hull.tricount() / 2 + 2,
+            mesh,
+        })
+    }
+}
+
+/// Splits a closed `mesh` into convex hulls
+///
+/// the most concave piece is repeatedly cut in two
+/// by the axis aligned plane leaving the least concave halves,
+/// then pieces whose hull has too many vertices are split
+/// while that lowers the vertex count  
+/// a piece that still has too many vertices gets its partial hull
+/// scaled up until it encloses the whole piece  
+/// returns closed hulls with outward facing triangles
+pub fn convex_decomposition(mesh: &TriMesh, options: DecomposeOptions) -> Vec<TriMesh> {
+    let whole = match Piece::new(mesh.clone()) {
+        Some(piece) => piece,
+        None => return Vec::new(),
+    };
+
+    let tolerance = options.concavity * (whole.concavity + whole.mesh.volume());
+    let mut pieces = vec![whole];
+    while pieces.len() < options.max_pieces {
+        let (i, piece) = pieces
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.concavity.total_cmp(&b.1.concavity))
+            .unwrap();
+
+        if piece.concavity <= tolerance {
+            break;
+        }
+
+        match split(&piece.mesh, options.resolution, |a, b| {
+            a.concavity + b.concavity
+        }) {
+            Some((above, below)) => {
+                pieces.swap_remove(i);
+                pieces.push(above);
+                pieces.push(below);
+            }
+            None => break,
+        }
+    }
+
+    // split pieces with too many hull vertices while the halves have fewer
+    let max_vertices = options.max_hull_vertices.max(4);
+    let mut i = 0;
+    while i < pieces.len() && pieces.len() < options.max_pieces {
+        let vertices = pieces[i].vertices;
+        let halves = if vertices > max_vertices {
+            split(&pieces[i].mesh, options.resolution, |a, b| {
+                a.vertices.max(b.vertices) as f32
+            })
+        } else {
+            None
+        };
+
+        match halves {
+            Some((above, below)) if above.vertices.max(below.vertices) < vertices => {
+                pieces[i] = above;
+                pieces.push(below);
+            }
+            _ => i += 1,
+        }
+    }
+
+    pieces
+        .iter()
+        .filter_map(|piece| {
+            let points: Vec<Vec3> = piece.mesh.triangles.iter().flat_map(|t| t.points).collect();
+            quickhull(&points, max_vertices).map(|hull| hull.enclosing_mesh(&points))
+        })
+        .collect()
+}
+
+impl TriMesh {
+    /// Splits this mesh into convex hulls,
+    /// see `hull::convex_decomposition`
+    pub fn convex_decomposition(&self, options: DecomposeOptions) -> Vec<TriMesh> {
+        convex_decomposition(self, options)
+    }
+}
+
+/// returns the two halves of the split of `mesh` with the lowest `cost`
+fn split(
+    mesh: &TriMesh,
+    resolution: usize,
+    cost: impl Fn(&Piece, &Piece) -> f32,
+) -> Option<(Piece, Piece)> {
+    let [min, max] = mesh.min_max()?;
+    let mut best: Option<(f32, Piece, Piece)> = None;
+    for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+        for i in 1..=resolution {
+            let t = i as f32 / (resolution + 1) as f32;
+            let plane = Plane::new(min.lerp(max, t), axis);
+            let (above, below) = mesh.slice(&plane);
+            if above.tricount() == 0 || below.tricount() == 0 {
+                continue;
+            }
+
+            let (above, below) = match (Piece::new(above), Piece::new(below)) {
+                (Some(above), Some(below)) => (above, below),
+                _ => continue,
+            };
+
+            let cost = cost(&above, &below);
+            if matches!(&best, Some(b) if b.0 <= cost) {
+                continue;
+            }
+
+            best = Some((cost, above, below));
+        }
+    }
+
+    best.map(|(_, above, below)| (above, below))
+}
@@ -0,0 +1,297 @@
+mod decompose;
+
+use std::collections::HashMap;
+
+use glam::{Vec2, Vec3};
+
+use crate::{meshes::TriMesh, triangle::Triangle};
+
+pub use decompose::{convex_decomposition, DecomposeOptions};
+
+/// returns the convex hull of `points` as a closed mesh
+/// with outward facing triangles
+///
+/// points on or inside the hull, including duplicates, are skipped  
+/// returns None if the points don't span a volume
+pub fn convex_hull(points: &[Vec3]) -> Option<TriMesh> {
+    quickhull(points, usize::MAX).map(|hull| hull.mesh(points))
+}
+
+impl TriMesh {
+    /// returns the convex hull of this mesh's points,
+    /// see `hull::convex_hull`
+    pub fn convex_hull(&self) -> Option<TriMesh> {
+        let points: Vec<Vec3> = self.triangles.iter().flat_map(|t| t.points).collect();
+        convex_hull(&points)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Face {
+    vertices: [usize; 3],
+    normal: Vec3,
+    offset: f32,
+    /// the points in front of this face
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+impl Face {
+    fn new(points: &[Vec3], vertices: [usize; 3]) -> Self {
+        let [a, b, c] = vertices.map(|i| points[i]);
+        let normal = (b - a).cross(c - a).normalize_or_zero();
+        Self {
+            vertices,
+            normal,
+            offset: normal.dot(a),
+            outside: Vec::new(),
+            alive: true,
+        }
+    }
+
+    fn distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) - self.offset
+    }
+
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Hull {
+    faces: Vec<Face>,
+    /// the face on the left of every directed edge
+    edges: HashMap<(usize, usize), usize>,
+    epsilon: f32,
+}
+
+/// Builds a hull with quickhull
+///
+/// stops adding points once the hull has `max_vertices`,
+/// leaving the furthest points outside
+fn quickhull(points: &[Vec3], max_vertices: usize) -> Option<Hull> {
+    // http://media.steampowered.com/apps/valve/2014/DirkGregorius_ImplementingQuickHull.pdf
+    let first = *points.first()?;
+    let (min, max) = points
+        .iter()
+        .fold((first, first), |(min, max), p| (min.min(*p), max.max(*p)));
+    let scale = max.abs().max(min.abs());
+    let epsilon = 3.0 * f32::EPSILON * (scale.x + scale.y + scale.z).max(1.0);
+
+    let simplex = initial_simplex(points, epsilon)?;
+    let mut hull = Hull {
+        epsilon,
+        ..Default::default()
+    };
+
+    let [a, b, c, d] = simplex;
+    let mut faces = [[a, b, c], [a, c, d], [a, d, b], [b, d, c]];
+    if Face::new(points, faces[0]).distance(points[d]) > 0.0 {
+        for f in &mut faces {
+            f.swap(1, 2);
+        }
+    }
+
+    let new: Vec<usize> = faces.iter().map(|f| hull.add_face(points, *f)).collect();
+    let all: Vec<usize> = (0..points.len()).filter(|i| !simplex.contains(i)).collect();
+    hull.assign(points, &new, all);
+
+    let mut vertices = 4;
+    let mut cursor = 0;
+    while cursor < hull.faces.len() && vertices < max_vertices {
+        let face = &hull.faces[cursor];
+        if !face.alive || face.outside.is_empty() {
+            cursor += 1;
+            continue;
+        }
+
+        let eye = *face
+            .outside
+            .iter()
+            .max_by(|a, b| {
+                face.distance(points[**a])
+                    .total_cmp(&face.distance(points[**b]))
+            })
+            .unwrap();
+
+        hull.add_point(points, cursor, eye);
+        vertices += 1;
+    }
+
+    Some(hull)
+}
+
+/// returns four points spanning a tetrahedron
+fn initial_simplex(points: &[Vec3], epsilon: f32) -> Option<[usize; 4]> {
+    // the two extreme points furthest apart
+    let mut extremes = [0; 6];
+    for (i, p) in points.iter().enumerate() {
+        for axis in 0..3 {
+            if p[axis] < points[extremes[axis * 2]][axis] {
+                extremes[axis * 2] = i;
+            }
+
+            if p[axis] > points[extremes[axis * 2 + 1]][axis] {
+                extremes[axis * 2 + 1] = i;
+            }
+        }
+    }
+
+    let mut best = (0.0, 0, 0);
+    for &i in &extremes {
+        for &j in &extremes {
+            let d = points[i].distance_squared(points[j]);
+            if d > best.0 {
+                best = (d, i, j);
+            }
+        }
+    }
+
+    let (a, b) = (best.1, best.2);
+    if best.0.sqrt() <= epsilon {
+        return None;
+    }
+
+    // the point furthest from the line
+    let line = (points[b] - points[a]).normalize();
+    let c = furthest(points, |p| {
+        let d = p - points[a];
+        (d - line * d.dot(line)).length()
+    });
+    let normal = (points[b] - points[a])
+        .cross(points[c] - points[a])
+        .try_normalize()?;
+
+    // the point furthest from the plane
+    let d = furthest(points, |p| normal.dot(p - points[a]).abs());
+    if normal.dot(points[d] - points[a]).abs() <= epsilon {
+        return None;
+    }
+
+    Some([a, b, c, d])
+}
+
+fn furthest(points: &[Vec3], distance: impl Fn(Vec3) -> f32) -> usize {
+    (0..points.len())
+        .max_by(|a, b| distance(points[*a]).total_cmp(&distance(points[*b])))
+        .unwrap()
+}
+
+impl Hull {
+    fn add_face(&mut self, points: &[Vec3], vertices: [usize; 3]) -> usize {
+        let face = Face::new(points, vertices);
+        let index = self.faces.len();
+        for edge in face.edges() {
+            self.edges.insert(edge, index);
+        }
+
+        self.faces.push(face);
+        index
+    }
+
+    /// Gives every point to the first new face it's in front of,
+    /// points behind every face are inside the hull
+    fn assign(&mut self, points: &[Vec3], faces: &[usize], candidates: Vec<usize>) {
+        for p in candidates {
+            for &f in faces {
+                if self.faces[f].distance(points[p]) > self.epsilon {
+                    self.faces[f].outside.push(p);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Adds `eye` to the hull,
+    /// replacing every face it can see with a cone of new faces
+    fn add_point(&mut self, points: &[Vec3], start: usize, eye: usize) {
+        let point = points[eye];
+
+        // Find the faces the eye can see
+        let mut visible = vec![start];
+        let mut stack = vec![start];
+        self.faces[start].alive = false;
+        while let Some(f) = stack.pop() {
+            for (a, b) in self.faces[f].edges() {
+                let n = self.edges[&(b, a)];
+                if self.faces[n].alive && self.faces[n].distance(point) > self.epsilon {
+                    self.faces[n].alive = false;
+                    visible.push(n);
+                    stack.push(n);
+                }
+            }
+        }
+
+        // The horizon is every edge between a visible and hidden face
+        let mut horizon = Vec::new();
+        for &f in &visible {
+            for (a, b) in self.faces[f].edges() {
+                if self.faces[self.edges[&(b, a)]].alive {
+                    horizon.push((a, b));
+                }
+            }
+        }
+
+        let mut orphans = Vec::new();
+        for &f in &visible {
+            orphans.append(&mut self.faces[f].outside);
+            for edge in self.faces[f].edges() {
+                if self.edges.get(&edge) == Some(&f) {
+                    self.edges.remove(&edge);
+                }
+            }
+        }
+
+        let new: Vec<usize> = horizon
+            .into_iter()
+            .map(|(a, b)| self.add_face(points, [a, b, eye]))
+            .collect();
+
+        orphans.retain(|p| *p != eye);
+        self.assign(points, &new, orphans);
+    }
+
+    fn mesh(&self, points: &[Vec3]) -> TriMesh {
+        self.scaled_mesh(points, Vec3::ZERO, 1.0)
+    }
+
+    /// returns the hull scaled about the center of its corners
+    /// until every point is on or inside it,
+    /// for hulls stopped before adding every point
+    fn enclosing_mesh(&self, points: &[Vec3]) -> TriMesh {
+        let faces: Vec<&Face> = self.faces.iter().filter(|f| f.alive).collect();
+        let corners = faces.iter().flat_map(|f| f.vertices.map(|i| points[i]));
+        let center = corners.sum::<Vec3>() / (3 * faces.len()) as f32;
+
+        // `p` is inside the face scaled by `s` when
+        // `normal.dot(p - center) <= s * (offset - normal.dot(center))`
+        let mut scale = 1.0_f32;
+        for face in &faces {
+            let depth = -face.distance(center);
+            for &p in points {
+                let distance = face.distance(p);
+                if distance > self.epsilon && depth > 0.0 {
+                    scale = scale.max(1.0 + distance / depth);
+                }
+            }
+        }
+
+        self.scaled_mesh(points, center, scale)
+    }
+
+    fn scaled_mesh(&self, points: &[Vec3], center: Vec3, scale: f32) -> TriMesh {
+        let triangles = self
+            .faces
+            .iter()
+            .filter(|f| f.alive)
+            .map(|f| {
+                let points = f.vertices.map(|i| center + (points[i] - center) * scale);
+                Triangle::new(points, [Vec2::ZERO; 3], f.normal)
+            })
+            .collect();
+
+        TriMesh::new(triangles)
+    }
+}
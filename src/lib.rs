@@ -3,12 +3,20 @@
 
 use glam::Vec3;
 
+pub mod attribute;
+pub mod bvh;
+mod csg;
+mod disjoint;
+pub mod hit;
+pub mod hull;
 pub mod meshes;
 pub mod plane;
 pub mod polygon;
 pub mod traits;
 pub mod triangle;
+mod triangulate;
 pub mod vertex;
+pub mod weld;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+
+use glam::{Vec2, Vec3};
+
+use crate::{
+    meshes::{Corner, Face as IndexFace, IndexMesh, PolyMesh, TriMesh},
+    polygon::Polygon,
+    vertex::Vertex as PolyVertex,
+    weld::Welder,
+};
+
+use super::{Face, FaceId, HalfEdge, HalfEdgeError, HalfEdgeId, HalfEdgeMesh, Vertex, VertexId};
+
+/// A corner of a face being built, it's vertex index, uv and normal
+type BuildCorner = (usize, Vec2, Vec3);
+
+/// A face being built, it's material and corners
+type BuildFace = (u32, Vec<BuildCorner>);
+
+impl HalfEdgeMesh {
+    /// Creates a mesh from `points` and faces of corners indexing into them
+    fn build(points: Vec<Vec3>, faces: Vec<BuildFace>) -> Result<Self, HalfEdgeError> {
+        let mut mesh = Self {
+            vertices: points
+                .into_iter()
+                .map(|point| Vertex {
+                    point,
+                    half_edge: None,
+                    removed: false,
+                })
+                .collect(),
+            half_edges: Vec::new(),
+            faces: Vec::with_capacity(faces.len()),
+        };
+
+        let mut edges: HashMap<(usize, usize), HalfEdgeId> = HashMap::new();
+        for (f, (material, corners)) in faces.iter().enumerate() {
+            for (i, &(a, ..)) in corners.iter().enumerate() {
+                if corners[i + 1..].iter().any(|c| c.0 == a) {
+                    return Err(HalfEdgeError::Degenerate { face: f });
+                }
+
+                let b = corners[(i + 1) % corners.len()].0;
+                if edges.contains_key(&(a, b)) {
+                    return Err(HalfEdgeError::NonManifold {
+                        face: f,
+                        edge: [a, b],
+                    });
+                }
+
+                edges.insert((a, b), HalfEdgeId(mesh.half_edges.len() + i));
+            }
+
+            let face = FaceId(mesh.faces.len());
+            let first = mesh.half_edges.len();
+            let len = corners.len();
+            mesh.faces.push(Face {
+                half_edge: HalfEdgeId(first),
+                material: *material,
+                removed: false,
+            });
+
+            for (i, &(origin, uv, normal)) in corners.iter().enumerate() {
+                mesh.half_edges.push(HalfEdge {
+                    origin: VertexId(origin),
+                    // linked below
+                    twin: HalfEdgeId(usize::MAX),
+                    next: HalfEdgeId(first + (i + 1) % len),
+                    prev: HalfEdgeId(first + (i + len - 1) % len),
+                    face: Some(face),
+                    uv,
+                    normal,
+                    removed: false,
+                });
+                mesh.vertices[origin].half_edge = Some(HalfEdgeId(first + i));
+            }
+        }
+
+        // Pair every half edge with it's opposite,
+        // adding boundary half edges where there isn't one
+        let mut keys: Vec<(usize, usize)> = edges.keys().copied().collect();
+        keys.sort_unstable();
+        let mut boundary: HashMap<usize, Vec<HalfEdgeId>> = HashMap::new();
+        for (a, b) in keys {
+            let h = edges[&(a, b)];
+            if let Some(&twin) = edges.get(&(b, a)) {
+                mesh.half_edges[h.0].twin = twin;
+                continue;
+            }
+
+            let twin = HalfEdgeId(mesh.half_edges.len());
+            mesh.half_edges.push(HalfEdge {
+                origin: VertexId(b),
+                twin: h,
+                next: twin,
+                prev: twin,
+                face: None,
+                uv: Vec2::ZERO,
+                normal: Vec3::ZERO,
+                removed: false,
+            });
+            mesh.half_edges[h.0].twin = twin;
+            mesh.vertices[b].half_edge = Some(twin);
+            boundary.entry(b).or_default().push(twin);
+        }
+
+        // Link the boundary half edges into loops
+        for i in 0..mesh.half_edges.len() {
+            let h = HalfEdgeId(i);
+            if !mesh.is_boundary(h) {
+                continue;
+            }
+
+            let end = mesh.destination(h);
+            if let Some(next) = boundary.get_mut(&end.0).and_then(|b| b.pop()) {
+                mesh.half_edges[h.0].next = next;
+                mesh.half_edges[next.0].prev = h;
+            }
+        }
+
+        Ok(mesh)
+    }
+
+    /// returns the faces as corners,
+    /// with uvs and normals deduplicated into `IndexMesh` style lists
+    fn corners(&self) -> (Vec<Vec2>, Vec<Vec3>, Vec<Vec<Corner>>) {
+        let mut uvs = Welder::new(0.0);
+        let mut normals = Welder::new(0.0);
+        let faces = self
+            .face_ids()
+            .map(|f| {
+                self.face_half_edges(f)
+                    .map(|h| {
+                        Corner::new(
+                            self.origin(h).0,
+                            uvs.insert(self.uv(h).extend(0.0)),
+                            normals.insert(self.normal(h)),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let uvs = uvs
+            .into_points()
+            .into_iter()
+            .map(|uv| uv.truncate())
+            .collect();
+        (uvs, normals.into_points(), faces)
+    }
+}
+
+impl TryFrom<IndexMesh> for HalfEdgeMesh {
+    type Error = HalfEdgeError;
+
+    /// keeps uvs and normals, tangents and attributes are dropped  
+    /// returns an error if a corner is out of range,
+    /// a face is degenerate or an edge is non manifold
+    fn try_from(value: IndexMesh) -> Result<Self, Self::Error> {
+        value.check_indices()?;
+        let faces = value
+            .faces
+            .iter()
+            .map(|f| {
+                let corners = f.corners.iter();
+                let corners =
+                    corners.map(|c| (c.position, value.uvs[c.uv], value.normals[c.normal]));
+                (f.material, corners.collect())
+            })
+            .collect();
+
+        Self::build(value.vertices, faces)
+    }
+}
+
+impl TryFrom<TriMesh> for HalfEdgeMesh {
+    type Error = HalfEdgeError;
+
+    /// Merges equal points into shared vertices
+    ///
+    /// returns an error if a triangle is degenerate or an edge is non manifold
+    fn try_from(value: TriMesh) -> Result<Self, Self::Error> {
+        let mut points = Welder::new(0.0);
+        let faces = value
+            .triangles
+            .iter()
+            .map(|t| {
+                let corners = (0..3).map(|i| (points.insert(t[i]), t.uvs[i], t.normal));
+                (t.material, corners.collect())
+            })
+            .collect();
+
+        Self::build(points.into_points(), faces)
+    }
+}
+
+impl TryFrom<PolyMesh> for HalfEdgeMesh {
+    type Error = HalfEdgeError;
+
+    /// Merges equal points into shared vertices
+    ///
+    /// returns an error if a polygon is degenerate or an edge is non manifold
+    fn try_from(value: PolyMesh) -> Result<Self, Self::Error> {
+        let mut points = Welder::new(0.0);
+        let faces = value
+            .polygons
+            .iter()
+            .map(|p| {
+                let corners = p.vertices.iter();
+                let corners = corners.map(|v| (points.insert(v.point), v.uv, v.normal));
+                (p.material, corners.collect())
+            })
+            .collect();
+
+        Self::build(points.into_points(), faces)
+    }
+}
+
+impl From<HalfEdgeMesh> for IndexMesh {
+    /// Splits every face into a fan of triangles
+    ///
+    /// removed vertices are kept so handles still index `vertices`
+    fn from(value: HalfEdgeMesh) -> Self {
+        let (uvs, normals, faces) = value.corners();
+        let mut out = IndexMesh::new(
+            value.vertices.iter().map(|v| v.point).collect(),
+            normals,
+            uvs,
+            Vec::new(),
+        );
+
+        for (f, corners) in value.face_ids().zip(faces) {
+            for i in 1..corners.len().saturating_sub(1) {
+                out.faces.push(IndexFace {
+                    material: value.material(f),
+                    ..IndexFace::new([corners[0], corners[i], corners[i + 1]])
+                });
+            }
+        }
+
+        out
+    }
+}
+
+impl From<HalfEdgeMesh> for TriMesh {
+    /// Splits every face into a fan of triangles
+    fn from(value: HalfEdgeMesh) -> Self {
+        TriMesh::try_from(IndexMesh::from(value))
+            .expect("half edges only index existing vertices, uvs and normals")
+    }
+}
+
+impl From<HalfEdgeMesh> for PolyMesh {
+    fn from(value: HalfEdgeMesh) -> Self {
+        let polygons = value
+            .face_ids()
+            .map(|f| Polygon {
+                vertices: value
+                    .face_half_edges(f)
+                    .map(|h| {
+                        PolyVertex::new(
+                            value.position(value.origin(h)),
+                            value.uv(h),
+                            value.normal(h),
+                        )
+                    })
+                    .collect(),
+                material: value.material(f),
+            })
+            .collect();
+
+        PolyMesh::new(polygons)
+    }
+}
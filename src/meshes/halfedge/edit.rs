@@ -0,0 +1,353 @@
+use std::collections::HashSet;
+
+use glam::{Vec2, Vec3};
+
+use super::{Face, FaceId, HalfEdge, HalfEdgeId, HalfEdgeMesh, Vertex, VertexId};
+
+impl HalfEdgeMesh {
+    /// Adds a vertex at `point` in the middle of the edge of `half_edge`
+    /// and returns it
+    ///
+    /// triangles next to the edge are split in two,
+    /// other polygons just gain a corner  
+    /// uvs and normals are interpolated along the edge
+    pub fn split_edge(&mut self, half_edge: HalfEdgeId, point: Vec3) -> VertexId {
+        let h = half_edge;
+        let t = self.twin(h);
+        let (a, b) = (self.position(self.origin(h)), self.position(self.origin(t)));
+        let ab = b - a;
+        let s = if ab == Vec3::ZERO {
+            0.5
+        } else {
+            ((point - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+        };
+
+        let vertex = VertexId(self.vertices.len());
+        self.vertices.push(Vertex {
+            point,
+            half_edge: None,
+            removed: false,
+        });
+
+        // h becomes a -> vertex and t becomes b -> vertex,
+        // followed by new half edges from vertex to b and to a
+        let after_h = self.insert_after(h, vertex, s);
+        let after_t = self.insert_after(t, vertex, 1.0 - s);
+        self.half_edges[h.0].twin = after_t;
+        self.half_edges[after_t.0].twin = h;
+        self.half_edges[t.0].twin = after_h;
+        self.half_edges[after_h.0].twin = t;
+        self.vertices[vertex.0].half_edge = [after_h, after_t]
+            .into_iter()
+            .find(|&e| self.is_boundary(e))
+            .or(Some(after_h));
+
+        for corner in [after_h, after_t] {
+            if self.face(corner).is_some() && self.face_half_edges_len(corner) == 4 {
+                let opposite = self.next(self.next(corner));
+                self.split_face(corner, opposite);
+            }
+        }
+
+        vertex
+    }
+
+    /// Replaces the edge of `half_edge` with one between
+    /// the other corners of the two triangles next to it
+    ///
+    /// returns false if it isn't between two triangles
+    /// or the new edge already exists
+    pub fn flip_edge(&mut self, half_edge: HalfEdgeId) -> bool {
+        let h = half_edge;
+        let t = self.twin(h);
+        let (f0, f1) = match self.edge_faces(h) {
+            [Some(f0), Some(f1)] => (f0, f1),
+            _ => return false,
+        };
+
+        if self.face_half_edges_len(h) != 3 || self.face_half_edges_len(t) != 3 {
+            return false;
+        }
+
+        let (h1, h2) = (self.next(h), self.prev(h));
+        let (t1, t2) = (self.next(t), self.prev(t));
+        let (a, b) = (self.origin(h), self.origin(t));
+        let (c, d) = (self.origin(h2), self.origin(t2));
+        if c == d || self.find_half_edge(c, d).is_some() {
+            return false;
+        }
+
+        // h becomes d -> c in (d, c, a) and t becomes c -> d in (c, d, b)
+        let (h2_uv, h2_normal) = (self.uv(h2), self.normal(h2));
+        let (t2_uv, t2_normal) = (self.uv(t2), self.normal(t2));
+        self.set_corner(h, d, t2_uv, t2_normal);
+        self.set_corner(t, c, h2_uv, h2_normal);
+        self.link_face(f0, [h, h2, t1]);
+        self.link_face(f1, [t, t2, h1]);
+
+        if self.vertex_half_edge(a) == Some(h) {
+            self.vertices[a.0].half_edge = Some(t1);
+        }
+
+        if self.vertex_half_edge(b) == Some(t) {
+            self.vertices[b.0].half_edge = Some(h1);
+        }
+
+        true
+    }
+
+    /// Merges the end of `half_edge` into it's start,
+    /// removing the edge and the triangles next to it  
+    /// returns the remaining vertex
+    ///
+    /// returns None without changing anything if the faces next to the edge
+    /// aren't triangles, the collapse would make the mesh non manifold
+    /// or it would flatten a closed surface of 4 or fewer vertices
+    pub fn collapse_edge(&mut self, half_edge: HalfEdgeId) -> Option<VertexId> {
+        let h = half_edge;
+        let t = self.twin(h);
+        let (a, b) = (self.origin(h), self.origin(t));
+        if !self.can_collapse(h) {
+            return None;
+        }
+
+        let outgoing: Vec<HalfEdgeId> = self.vertex_half_edges(b).collect();
+        for &o in &outgoing {
+            self.half_edges[o.0].origin = a;
+        }
+
+        let mut touched = vec![a];
+        for e in [h, t] {
+            match self.face(e) {
+                Some(face) => touched.push(self.remove_triangle(e, face)),
+                None => {
+                    let (prev, next) = (self.prev(e), self.next(e));
+                    self.half_edges[prev.0].next = next;
+                    self.half_edges[next.0].prev = prev;
+                }
+            }
+        }
+
+        for e in [h, t] {
+            self.half_edges[e.0].removed = true;
+        }
+
+        self.vertices[b.0].removed = true;
+        self.vertices[b.0].half_edge = None;
+
+        let remaining = outgoing
+            .into_iter()
+            .chain(self.vertex_half_edges_unchecked(a))
+            .find(|&o| !self.half_edges[o.0].removed);
+        self.vertices[a.0].half_edge = remaining;
+        for v in touched {
+            self.fix_vertex(v);
+        }
+
+        Some(a)
+    }
+
+    fn can_collapse(&self, h: HalfEdgeId) -> bool {
+        let t = self.twin(h);
+        let (a, b) = (self.origin(h), self.origin(t));
+        let mut opposite = HashSet::new();
+        let mut faces = 0;
+        for e in [h, t] {
+            if self.face(e).is_none() {
+                continue;
+            }
+
+            if self.face_half_edges_len(e) != 3 {
+                return false;
+            }
+
+            // a triangle hanging on by a single edge would leave a dangling edge
+            let (next, prev) = (self.next(e), self.prev(e));
+            if self.is_boundary(self.twin(next)) && self.is_boundary(self.twin(prev)) {
+                return false;
+            }
+
+            opposite.insert(self.origin(prev));
+            faces += 1;
+        }
+
+        // two triangles over the same three vertices would collapse into nothing
+        if faces == 2 && opposite.len() == 1 {
+            return false;
+        }
+
+        // a tetrahedron would collapse into two triangles back to back
+        if self.is_small_closed(a, 4) {
+            return false;
+        }
+
+        // an interior edge between two boundaries would pinch the surface
+        if !self.is_boundary_edge(h) && self.is_boundary_vertex(a) && self.is_boundary_vertex(b) {
+            return false;
+        }
+
+        let ring: HashSet<VertexId> = self.vertex_ring(a).collect();
+        self.vertex_ring(b)
+            .filter(|v| ring.contains(v))
+            .all(|v| opposite.contains(&v))
+    }
+
+    /// returns whether `vertex` is on a closed surface of at most `max` vertices
+    fn is_small_closed(&self, vertex: VertexId, max: usize) -> bool {
+        let mut seen = HashSet::from([vertex]);
+        let mut stack = vec![vertex];
+        while let Some(v) = stack.pop() {
+            if self.is_boundary_vertex(v) {
+                return false;
+            }
+
+            for u in self.vertex_ring(v) {
+                if seen.insert(u) {
+                    if seen.len() > max {
+                        return false;
+                    }
+
+                    stack.push(u);
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Removes the triangle `face` of `e` whose start was merged into it's end,
+    /// pairing the twins of the other two edges  
+    /// returns the corner opposite to `e`
+    fn remove_triangle(&mut self, e: HalfEdgeId, face: FaceId) -> VertexId {
+        let (next, prev) = (self.next(e), self.prev(e));
+        let (x1, x2) = (self.twin(next), self.twin(prev));
+        let c = self.origin(prev);
+        self.half_edges[x1.0].twin = x2;
+        self.half_edges[x2.0].twin = x1;
+
+        // x1 now runs from c to the remaining vertex, x2 back
+        self.vertices[c.0].half_edge = Some(x1);
+        let a = self.origin(x2);
+        self.vertices[a.0].half_edge = Some(x2);
+
+        for r in [next, prev] {
+            self.half_edges[r.0].removed = true;
+        }
+
+        self.faces[face.0].removed = true;
+        c
+    }
+
+    /// Prefers a boundary half edge for `vertex` so iteration covers it's whole fan
+    fn fix_vertex(&mut self, vertex: VertexId) {
+        let boundary = self
+            .vertex_half_edges(vertex)
+            .find(|&h| self.is_boundary(h));
+        if boundary.is_some() {
+            self.vertices[vertex.0].half_edge = boundary;
+        }
+    }
+
+    /// the outgoing half edges of `vertex` without following removed ones
+    fn vertex_half_edges_unchecked(&self, vertex: VertexId) -> Vec<HalfEdgeId> {
+        match self.vertex_half_edge(vertex) {
+            Some(h) if !self.half_edges[h.0].removed => self.vertex_half_edges(vertex).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Inserts a half edge starting at `vertex` after `h` in it's loop  
+    /// it's corner is interpolated `s` of the way along `h`
+    fn insert_after(&mut self, h: HalfEdgeId, vertex: VertexId, s: f32) -> HalfEdgeId {
+        let next = self.next(h);
+        let id = HalfEdgeId(self.half_edges.len());
+        self.half_edges.push(HalfEdge {
+            origin: vertex,
+            twin: id,
+            next,
+            prev: h,
+            face: self.face(h),
+            uv: self.uv(h).lerp(self.uv(next), s),
+            normal: self
+                .normal(h)
+                .lerp(self.normal(next), s)
+                .normalize_or_zero(),
+            removed: false,
+        });
+        self.half_edges[h.0].next = id;
+        self.half_edges[next.0].prev = id;
+        id
+    }
+
+    /// Splits the face of `from` and `to` with a new edge between their starts
+    fn split_face(&mut self, from: HalfEdgeId, to: HalfEdgeId) {
+        let face = self.face(from).expect("only faces are split");
+        let (from_prev, to_prev) = (self.prev(from), self.prev(to));
+        let new_face = FaceId(self.faces.len());
+        let (n1, n2) = (
+            HalfEdgeId(self.half_edges.len()),
+            HalfEdgeId(self.half_edges.len() + 1),
+        );
+
+        // the new face runs from `to` around to `from`, closed by n1
+        self.half_edges.push(HalfEdge {
+            origin: self.origin(from),
+            twin: n2,
+            next: to,
+            prev: from_prev,
+            face: Some(new_face),
+            uv: self.uv(from),
+            normal: self.normal(from),
+            removed: false,
+        });
+        self.half_edges.push(HalfEdge {
+            origin: self.origin(to),
+            twin: n1,
+            next: from,
+            prev: to_prev,
+            face: Some(face),
+            uv: self.uv(to),
+            normal: self.normal(to),
+            removed: false,
+        });
+
+        self.half_edges[from_prev.0].next = n1;
+        self.half_edges[to.0].prev = n1;
+        self.half_edges[to_prev.0].next = n2;
+        self.half_edges[from.0].prev = n2;
+
+        self.faces.push(Face {
+            half_edge: to,
+            material: self.material(face),
+            removed: false,
+        });
+        self.faces[face.0].half_edge = from;
+        for e in self.loop_half_edges(to).collect::<Vec<_>>() {
+            self.half_edges[e.0].face = Some(new_face);
+        }
+    }
+
+    fn set_corner(&mut self, h: HalfEdgeId, origin: VertexId, uv: Vec2, normal: Vec3) {
+        let e = &mut self.half_edges[h.0];
+        e.origin = origin;
+        e.uv = uv;
+        e.normal = normal;
+    }
+
+    /// Links `edges` into a loop around `face`
+    fn link_face(&mut self, face: FaceId, edges: [HalfEdgeId; 3]) {
+        for i in 0..3 {
+            let e = &mut self.half_edges[edges[i].0];
+            e.next = edges[(i + 1) % 3];
+            e.prev = edges[(i + 2) % 3];
+            e.face = Some(face);
+        }
+
+        self.faces[face.0].half_edge = edges[0];
+    }
+
+    /// returns the amount of half edges in the loop of `h`
+    fn face_half_edges_len(&self, h: HalfEdgeId) -> usize {
+        self.loop_half_edges(h).count()
+    }
+}
@@ -0,0 +1,37 @@
+use std::fmt;
+
+use crate::meshes::IndexError;
+
+/// An error from building a `HalfEdgeMesh`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HalfEdgeError {
+    /// a face uses the same vertex twice
+    Degenerate { face: usize },
+    /// an edge is used twice in the same direction,
+    /// either by more than 2 faces or by faces with opposite winding
+    NonManifold { face: usize, edge: [usize; 2] },
+    /// a corner of an `IndexMesh` is out of range
+    Index(IndexError),
+}
+
+impl fmt::Display for HalfEdgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Degenerate { face } => write!(f, "face {face} uses a vertex more than once"),
+            Self::NonManifold { face, edge } => write!(
+                f,
+                "face {face}: edge from {} to {} is already used in that direction",
+                edge[0], edge[1]
+            ),
+            Self::Index(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for HalfEdgeError {}
+
+impl From<IndexError> for HalfEdgeError {
+    fn from(value: IndexError) -> Self {
+        Self::Index(value)
+    }
+}
@@ -0,0 +1,289 @@
+mod convert;
+mod edit;
+mod error;
+
+use glam::{Vec2, Vec3};
+
+pub use error::HalfEdgeError;
+
+/// A handle to a vertex of a `HalfEdgeMesh`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VertexId(pub usize);
+
+/// A handle to a half edge of a `HalfEdgeMesh`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HalfEdgeId(pub usize);
+
+/// A handle to a face of a `HalfEdgeMesh`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FaceId(pub usize);
+
+/// A polygon mesh storing every edge as two opposite half edges
+///
+/// each half edge knows it's twin, the next and previous half edges
+/// around it's face and the vertex it starts at  
+/// open boundaries are made of half edges without a face
+///
+/// removed elements are only marked as removed,
+/// so handles stay valid through edits
+#[derive(Clone, Debug, Default)]
+pub struct HalfEdgeMesh {
+    vertices: Vec<Vertex>,
+    half_edges: Vec<HalfEdge>,
+    faces: Vec<Face>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Vertex {
+    point: Vec3,
+    /// an outgoing half edge, a boundary one if there is one
+    half_edge: Option<HalfEdgeId>,
+    removed: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct HalfEdge {
+    origin: VertexId,
+    twin: HalfEdgeId,
+    next: HalfEdgeId,
+    prev: HalfEdgeId,
+    face: Option<FaceId>,
+    /// the uv and normal of the face's corner at `origin`
+    uv: Vec2,
+    normal: Vec3,
+    removed: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    half_edge: HalfEdgeId,
+    material: u32,
+    removed: bool,
+}
+
+impl HalfEdgeMesh {
+    /// returns the amount of vertices that aren't removed
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.iter().filter(|v| !v.removed).count()
+    }
+
+    /// returns the amount of edges that aren't removed,
+    /// counting each pair of half edges once
+    pub fn edge_count(&self) -> usize {
+        self.half_edges.iter().filter(|h| !h.removed).count() / 2
+    }
+
+    /// returns the amount of faces that aren't removed
+    pub fn face_count(&self) -> usize {
+        self.faces.iter().filter(|f| !f.removed).count()
+    }
+
+    /// returns every vertex that isn't removed
+    pub fn vertex_ids(&self) -> impl Iterator<Item = VertexId> + '_ {
+        (0..self.vertices.len())
+            .filter(|&i| !self.vertices[i].removed)
+            .map(VertexId)
+    }
+
+    /// returns every half edge that isn't removed
+    pub fn half_edge_ids(&self) -> impl Iterator<Item = HalfEdgeId> + '_ {
+        (0..self.half_edges.len())
+            .filter(|&i| !self.half_edges[i].removed)
+            .map(HalfEdgeId)
+    }
+
+    /// returns every face that isn't removed
+    pub fn face_ids(&self) -> impl Iterator<Item = FaceId> + '_ {
+        (0..self.faces.len())
+            .filter(|&i| !self.faces[i].removed)
+            .map(FaceId)
+    }
+
+    /// returns whether `vertex` was removed by an edit
+    pub fn is_vertex_removed(&self, vertex: VertexId) -> bool {
+        self.vertices[vertex.0].removed
+    }
+
+    /// returns whether `half_edge` was removed by an edit
+    pub fn is_half_edge_removed(&self, half_edge: HalfEdgeId) -> bool {
+        self.half_edges[half_edge.0].removed
+    }
+
+    /// returns whether `face` was removed by an edit
+    pub fn is_face_removed(&self, face: FaceId) -> bool {
+        self.faces[face.0].removed
+    }
+
+    pub fn position(&self, vertex: VertexId) -> Vec3 {
+        self.vertices[vertex.0].point
+    }
+
+    pub fn set_position(&mut self, vertex: VertexId, point: Vec3) {
+        self.vertices[vertex.0].point = point;
+    }
+
+    /// returns an outgoing half edge of `vertex`,
+    /// a boundary one if `vertex` is on a boundary  
+    /// returns None for vertices without edges
+    pub fn vertex_half_edge(&self, vertex: VertexId) -> Option<HalfEdgeId> {
+        self.vertices[vertex.0].half_edge
+    }
+
+    /// returns a half edge of `face`
+    pub fn face_half_edge(&self, face: FaceId) -> HalfEdgeId {
+        self.faces[face.0].half_edge
+    }
+
+    /// returns the vertex `half_edge` starts at
+    pub fn origin(&self, half_edge: HalfEdgeId) -> VertexId {
+        self.half_edges[half_edge.0].origin
+    }
+
+    /// returns the vertex `half_edge` points to
+    pub fn destination(&self, half_edge: HalfEdgeId) -> VertexId {
+        self.origin(self.twin(half_edge))
+    }
+
+    /// returns the opposite half edge
+    pub fn twin(&self, half_edge: HalfEdgeId) -> HalfEdgeId {
+        self.half_edges[half_edge.0].twin
+    }
+
+    /// returns the next half edge around the same face or boundary
+    pub fn next(&self, half_edge: HalfEdgeId) -> HalfEdgeId {
+        self.half_edges[half_edge.0].next
+    }
+
+    /// returns the previous half edge around the same face or boundary
+    pub fn prev(&self, half_edge: HalfEdgeId) -> HalfEdgeId {
+        self.half_edges[half_edge.0].prev
+    }
+
+    /// returns the face of `half_edge`, None on a boundary
+    pub fn face(&self, half_edge: HalfEdgeId) -> Option<FaceId> {
+        self.half_edges[half_edge.0].face
+    }
+
+    /// returns the uv of the corner at the start of `half_edge`
+    pub fn uv(&self, half_edge: HalfEdgeId) -> Vec2 {
+        self.half_edges[half_edge.0].uv
+    }
+
+    /// returns the normal of the corner at the start of `half_edge`
+    pub fn normal(&self, half_edge: HalfEdgeId) -> Vec3 {
+        self.half_edges[half_edge.0].normal
+    }
+
+    pub fn set_uv(&mut self, half_edge: HalfEdgeId, uv: Vec2) {
+        self.half_edges[half_edge.0].uv = uv;
+    }
+
+    pub fn set_normal(&mut self, half_edge: HalfEdgeId, normal: Vec3) {
+        self.half_edges[half_edge.0].normal = normal;
+    }
+
+    /// returns the material or group `face` belongs to
+    pub fn material(&self, face: FaceId) -> u32 {
+        self.faces[face.0].material
+    }
+
+    pub fn set_material(&mut self, face: FaceId, material: u32) {
+        self.faces[face.0].material = material;
+    }
+
+    /// returns whether `half_edge` is on an open boundary
+    pub fn is_boundary(&self, half_edge: HalfEdgeId) -> bool {
+        self.face(half_edge).is_none()
+    }
+
+    /// returns whether the edge of `half_edge` is on an open boundary
+    pub fn is_boundary_edge(&self, half_edge: HalfEdgeId) -> bool {
+        self.is_boundary(half_edge) || self.is_boundary(self.twin(half_edge))
+    }
+
+    /// returns whether `vertex` is on an open boundary
+    pub fn is_boundary_vertex(&self, vertex: VertexId) -> bool {
+        self.vertex_half_edge(vertex)
+            .is_some_and(|h| self.is_boundary(h))
+    }
+
+    /// returns the half edge from `from` to `to` if there is one
+    pub fn find_half_edge(&self, from: VertexId, to: VertexId) -> Option<HalfEdgeId> {
+        self.vertex_half_edges(from)
+            .find(|&h| self.destination(h) == to)
+    }
+
+    /// returns the outgoing half edges of `vertex`
+    pub fn vertex_half_edges(&self, vertex: VertexId) -> impl Iterator<Item = HalfEdgeId> + '_ {
+        let first = self.vertex_half_edge(vertex);
+        let mut current = first;
+        std::iter::from_fn(move || {
+            let h = current?;
+            let next = self.next(self.twin(h));
+            current = (Some(next) != first).then_some(next);
+            Some(h)
+        })
+    }
+
+    /// returns the vertices connected to `vertex` by an edge
+    pub fn vertex_ring(&self, vertex: VertexId) -> impl Iterator<Item = VertexId> + '_ {
+        self.vertex_half_edges(vertex).map(|h| self.destination(h))
+    }
+
+    /// returns the faces around `vertex`
+    pub fn vertex_faces(&self, vertex: VertexId) -> impl Iterator<Item = FaceId> + '_ {
+        self.vertex_half_edges(vertex).filter_map(|h| self.face(h))
+    }
+
+    /// returns the half edges around `face` in order
+    pub fn face_half_edges(&self, face: FaceId) -> impl Iterator<Item = HalfEdgeId> + '_ {
+        self.loop_half_edges(self.face_half_edge(face))
+    }
+
+    /// returns the vertices of `face` in order
+    pub fn face_vertices(&self, face: FaceId) -> impl Iterator<Item = VertexId> + '_ {
+        self.face_half_edges(face).map(|h| self.origin(h))
+    }
+
+    /// returns the faces sharing an edge with `face`
+    pub fn face_neighbours(&self, face: FaceId) -> impl Iterator<Item = FaceId> + '_ {
+        self.face_half_edges(face)
+            .filter_map(|h| self.face(self.twin(h)))
+    }
+
+    /// returns the faces on both sides of the edge of `half_edge`
+    pub fn edge_faces(&self, half_edge: HalfEdgeId) -> [Option<FaceId>; 2] {
+        [self.face(half_edge), self.face(self.twin(half_edge))]
+    }
+
+    /// returns every open boundary as a loop of half edges without a face
+    pub fn boundary_loops(&self) -> Vec<Vec<HalfEdgeId>> {
+        let mut visited = vec![false; self.half_edges.len()];
+        let mut out = Vec::new();
+        for h in self.half_edge_ids() {
+            if visited[h.0] || !self.is_boundary(h) {
+                continue;
+            }
+
+            let boundary: Vec<HalfEdgeId> = self.loop_half_edges(h).collect();
+            for b in &boundary {
+                visited[b.0] = true;
+            }
+
+            out.push(boundary);
+        }
+
+        out
+    }
+
+    /// returns the half edges following `first` until it's reached again
+    fn loop_half_edges(&self, first: HalfEdgeId) -> impl Iterator<Item = HalfEdgeId> + '_ {
+        let mut current = Some(first);
+        std::iter::from_fn(move || {
+            let h = current?;
+            let next = self.next(h);
+            current = (next != first).then_some(next);
+            Some(h)
+        })
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use glam::Vec4;
+
+use crate::vertex::Vertex;
+
+use super::{Corner, Face, IndexError, IndexMesh};
+
+/// An interleaved vertex buffer with a single index per vertex  
+/// ready to be uploaded to a GPU
+#[derive(Clone, Debug, Default)]
+pub struct VertexBuffer<I> {
+    pub vertices: Vec<Vertex>,
+    /// one per vertex when the mesh has tangents,
+    /// zero for corners without one
+    pub tangents: Vec<Vec4>,
+    /// three indices per triangle
+    pub indices: Vec<I>,
+}
+
+impl IndexMesh {
+    /// Creates a `VertexBuffer` from this mesh
+    /// with one vertex for every unique corner
+    ///
+    /// use `u16` or `u32` for `I`,
+    /// returns an error if there's a vertex `I` can't index
+    pub fn vertex_buffer<I: TryFrom<usize>>(&self) -> Result<VertexBuffer<I>, IndexError> {
+        self.check_indices()?;
+
+        let mut out = VertexBuffer {
+            vertices: Vec::new(),
+            tangents: Vec::new(),
+            indices: Vec::with_capacity(self.faces.len() * 3),
+        };
+        self.extend_buffer(&mut out, &self.faces, &mut HashMap::new())?;
+
+        Ok(out)
+    }
+
+    /// Adds the indices of `faces` to `out`
+    /// along with any of their corners not in `corners` yet
+    pub(super) fn extend_buffer<I: TryFrom<usize>>(
+        &self,
+        out: &mut VertexBuffer<I>,
+        faces: &[Face],
+        corners: &mut HashMap<Corner, usize>,
+    ) -> Result<(), IndexError> {
+        for face in faces {
+            for corner in face.corners {
+                let index = *corners.entry(corner).or_insert_with(|| {
+                    out.vertices.push(Vertex::new(
+                        self.vertices[corner.position],
+                        self.uvs[corner.uv],
+                        self.normals[corner.normal],
+                    ));
+                    if !self.tangents.is_empty() {
+                        let tangent = corner.tangent.map(|t| self.tangents[t]);
+                        out.tangents.push(tangent.unwrap_or(Vec4::ZERO));
+                    }
+
+                    out.vertices.len() - 1
+                });
+
+                let index = I::try_from(index).map_err(|_| IndexError::TooManyVertices {
+                    count: out.vertices.len(),
+                })?;
+                out.indices.push(index);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// The attribute a `Corner` indexes into
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Attribute {
+    Position,
+    Uv,
+    Normal,
+    Tangent,
+    Custom,
+}
+
+/// An error from reading an `IndexMesh`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexError {
+    /// a corner references an element that doesn't exist
+    OutOfRange {
+        face: usize,
+        corner: usize,
+        attribute: Attribute,
+        index: usize,
+        len: usize,
+    },
+    /// there are more unique corners than the index type can address
+    TooManyVertices { count: usize },
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange {
+                face,
+                corner,
+                attribute,
+                index,
+                len,
+            } => write!(
+                f,
+                "face {face} corner {corner}: {attribute:?} index {index} is out of range (len {len})"
+            ),
+            Self::TooManyVertices { count } => {
+                write!(f, "{count} vertices can't be addressed by the index type")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IndexError {}
@@ -0,0 +1,67 @@
+use std::ops::{Index, IndexMut};
+
+/// The indices of a single triangle corner  
+/// into an `IndexMesh`'s vertices, uvs, normals, tangents and attributes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Corner {
+    pub position: usize,
+    pub uv: usize,
+    pub normal: usize,
+    /// None when the corner has no tangent
+    pub tangent: Option<usize>,
+    /// the attribute values of this corner,
+    /// None for default values
+    pub attribute: Option<usize>,
+}
+
+impl Corner {
+    pub fn new(position: usize, uv: usize, normal: usize) -> Self {
+        Self {
+            position,
+            uv,
+            normal,
+            tangent: None,
+            attribute: None,
+        }
+    }
+}
+
+/// A triangle in an `IndexMesh`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Face {
+    pub corners: [Corner; 3],
+    /// the material or group this face belongs to
+    pub material: u32,
+}
+
+impl Face {
+    pub fn new(corners: [Corner; 3]) -> Self {
+        Self {
+            corners,
+            material: 0,
+        }
+    }
+
+    /// returns the position indices of this face
+    pub fn positions(&self) -> [usize; 3] {
+        self.corners.map(|c| c.position)
+    }
+}
+
+impl Index<usize> for Face {
+    type Output = Corner;
+
+    /// returns a reference to the nth corner
+    ///
+    /// ### Panic
+    /// panics if the index is above 2
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.corners[index]
+    }
+}
+
+impl IndexMut<usize> for Face {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.corners[index]
+    }
+}
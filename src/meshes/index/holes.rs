@@ -0,0 +1,437 @@
+use std::collections::{HashMap, HashSet};
+
+use glam::{Vec2, Vec3};
+
+use crate::{plane::Plane, triangulate::triangulate};
+
+use super::{Corner, Face, IndexMesh};
+
+/// How `IndexMesh::fill_holes` splits a hole into triangles
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FillMethod {
+    /// the triangles with the least total area,
+    /// takes cubic time in the amount of edges around the hole
+    #[default]
+    MinimumArea,
+    /// ear clipping after flattening the hole onto it's average plane
+    EarClipping,
+}
+
+/// Options for `IndexMesh::fill_holes`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FillOptions {
+    pub method: FillMethod,
+    /// only fills holes with at most this many edges
+    pub max_edges: Option<usize>,
+    /// only fills holes with at most this perimeter
+    pub max_perimeter: Option<f32>,
+    /// adds vertices until the new edges are about as long as the edges around the hole
+    pub refine: bool,
+    /// moves the added vertices so the patch continues the curvature around it,
+    /// only does something with `refine`
+    pub fair: bool,
+}
+
+/// the most times `refine` splits and flips the edges of a patch
+const REFINE_PASSES: usize = 16;
+/// the amount of Gauss-Seidel iterations used for fairing
+const FAIR_ITERATIONS: usize = 200;
+
+impl IndexMesh {
+    /// returns the vertices around every hole,
+    /// running the way the faces filling them should wind
+    ///
+    /// holes touching at a vertex are separate loops,
+    /// no loop passes through a vertex twice
+    pub fn hole_loops(&self) -> Vec<Vec<usize>> {
+        let mut used: HashMap<(usize, usize), usize> = HashMap::new();
+        for face in &self.faces {
+            let p = face.positions();
+            for i in 0..3 {
+                *used.entry((p[i], p[(i + 1) % 3])).or_default() += 1;
+            }
+        }
+
+        // the edges of a hole run against the faces around it
+        let mut next: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut edges: Vec<(usize, usize)> = used
+            .iter()
+            .filter(|&(&(a, b), &count)| count == 1 && !used.contains_key(&(b, a)))
+            .map(|(&(a, b), _)| (b, a))
+            .collect();
+        edges.sort_unstable();
+        for &(a, b) in edges.iter().rev() {
+            next.entry(a).or_default().push(b);
+        }
+
+        let mut out = Vec::new();
+        for (start, _) in edges {
+            let mut boundary = vec![start];
+            let mut current = start;
+            while let Some(v) = next.get_mut(&current).and_then(|n| n.pop()) {
+                if v == start {
+                    out.extend(split_loop(boundary));
+                    break;
+                }
+
+                boundary.push(v);
+                current = v;
+            }
+        }
+
+        out
+    }
+
+    /// Fills every hole within the limits of `options` with new faces  
+    /// returns the amount of filled holes
+    ///
+    /// new faces use the uvs of the faces around the hole,
+    /// added vertices get interpolated uvs  
+    /// every new face gets it's own normal
+    pub fn fill_holes(&mut self, options: FillOptions) -> usize {
+        let mut filled = 0;
+        for boundary in self.hole_loops() {
+            if self.fill_hole(&boundary, options) > 0 {
+                filled += 1;
+            }
+        }
+
+        filled
+    }
+
+    /// Fills the hole inside `boundary` if it's within the limits of `options`  
+    /// returns the amount of added faces
+    pub(crate) fn fill_hole(&mut self, boundary: &[usize], options: FillOptions) -> usize {
+        let points: Vec<Vec3> = boundary.iter().map(|&v| self.vertices[v]).collect();
+        let perimeter: f32 = (0..points.len())
+            .map(|i| points[i].distance(points[(i + 1) % points.len()]))
+            .sum();
+        if boundary.len() < 3
+            || options.max_edges.is_some_and(|max| boundary.len() > max)
+            || options.max_perimeter.is_some_and(|max| perimeter > max)
+        {
+            return 0;
+        }
+
+        let local = match options.method {
+            FillMethod::MinimumArea => minimum_area(&points),
+            FillMethod::EarClipping => ear_clipping(&points),
+        };
+        let mut patch: Vec<[usize; 3]> = local.iter().map(|t| t.map(|i| boundary[i])).collect();
+
+        // keep the uvs and material of the faces around the hole
+        let mut uvs: HashMap<usize, usize> = HashMap::new();
+        for corner in self.faces.iter().flat_map(|f| f.corners) {
+            uvs.entry(corner.position).or_insert(corner.uv);
+        }
+
+        let material = self
+            .faces
+            .iter()
+            .find(|f| f.positions().contains(&boundary[0]))
+            .map_or(0, |f| f.material);
+
+        let first = self.vertices.len();
+        if options.refine {
+            let length = perimeter / boundary.len() as f32;
+            self.refine(&mut patch, &mut uvs, length);
+            if options.fair {
+                self.fair(&patch, first);
+            }
+        }
+
+        for &triangle in &patch {
+            let [a, b, c] = triangle.map(|v| self.vertices[v]);
+            let n = self.normals.len();
+            self.normals.push((b - a).cross(c - a).normalize_or_zero());
+
+            let corners = triangle.map(|v| Corner::new(v, uvs.get(&v).copied().unwrap_or(0), n));
+            self.faces.push(Face {
+                material,
+                ..Face::new(corners)
+            });
+        }
+
+        patch.len()
+    }
+
+    /// Splits the long edges inside `patch` and flips edges
+    /// to make it's triangles rounder
+    fn refine(
+        &mut self,
+        patch: &mut Vec<[usize; 3]>,
+        uvs: &mut HashMap<usize, usize>,
+        length: f32,
+    ) {
+        let mesh_edges: HashSet<(usize, usize)> = self
+            .faces
+            .iter()
+            .flat_map(|f| {
+                let [a, b, c] = f.positions();
+                [key(a, b), key(b, c), key(c, a)]
+            })
+            .collect();
+
+        for _ in 0..REFINE_PASSES {
+            let edges = inner_edges(patch);
+            let mut long: Vec<((usize, usize), f32)> = edges
+                .keys()
+                .map(|&(a, b)| ((a, b), self.vertices[a].distance(self.vertices[b])))
+                .filter(|&(_, l)| l > length * 4.0 / 3.0)
+                .collect();
+            if long.is_empty() {
+                break;
+            }
+
+            long.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+            let mut split = HashSet::new();
+            for ((a, b), _) in long {
+                let faces = match edges.get(&(a, b)) {
+                    Some(&faces) => faces,
+                    None => continue,
+                };
+
+                // each triangle is split at most once per pass
+                if faces.iter().any(|&f| !split.insert(f)) {
+                    continue;
+                }
+
+                let m = self.vertices.len();
+                self.vertices
+                    .push((self.vertices[a] + self.vertices[b]) * 0.5);
+                let uv = |v| uvs.get(&v).map(|&uv| self.uvs[uv]).unwrap_or_default();
+                let middle = (uv(a) + uv(b)) * 0.5;
+                uvs.insert(m, self.uvs.len());
+                self.uvs.push(middle);
+
+                for f in faces {
+                    let t = patch[f];
+                    let i = (0..3)
+                        .find(|&i| key(t[i], t[(i + 1) % 3]) == (a, b))
+                        .unwrap_or(0);
+                    let [p, q, r] = [t[i], t[(i + 1) % 3], t[(i + 2) % 3]];
+                    patch[f] = [p, m, r];
+                    patch.push([m, q, r]);
+                }
+            }
+
+            self.relax(patch, &mesh_edges);
+        }
+    }
+
+    /// Flips edges inside `patch` whose opposite angles add up to more than 180°
+    fn relax(&self, patch: &mut [[usize; 3]], mesh_edges: &HashSet<(usize, usize)>) {
+        for _ in 0..REFINE_PASSES {
+            let mut flipped = false;
+            let mut edges: Vec<((usize, usize), [usize; 2])> =
+                inner_edges(patch).into_iter().collect();
+            edges.sort_unstable();
+            let mut used: HashSet<(usize, usize)> = patch
+                .iter()
+                .flat_map(|t| [key(t[0], t[1]), key(t[1], t[2]), key(t[2], t[0])])
+                .collect();
+
+            for ((a, b), [f, g]) in edges {
+                // earlier flips may have changed these triangles
+                if [f, g]
+                    .iter()
+                    .any(|&t| !patch[t].contains(&a) || !patch[t].contains(&b))
+                {
+                    continue;
+                }
+
+                let (c, d) = (opposite(patch[f], a, b), opposite(patch[g], a, b));
+                if used.contains(&key(c, d)) || mesh_edges.contains(&key(c, d)) {
+                    continue;
+                }
+
+                let angle = |v: usize| {
+                    let p = self.vertices[v];
+                    (self.vertices[a] - p).angle_between(self.vertices[b] - p)
+                };
+                if angle(c) + angle(d) <= std::f32::consts::PI + 1e-4 {
+                    continue;
+                }
+
+                // keep the winding of f, which runs a -> b or b -> a
+                let i = (0..3).position(|i| patch[f][i] == c).unwrap_or(0);
+                let [_, x, y] = [patch[f][i], patch[f][(i + 1) % 3], patch[f][(i + 2) % 3]];
+                patch[f] = [c, x, d];
+                patch[g] = [c, d, y];
+                used.remove(&(a, b));
+                used.insert(key(c, d));
+                flipped = true;
+            }
+
+            if !flipped {
+                break;
+            }
+        }
+    }
+
+    /// Moves the vertices from `first` on to minimize the change in curvature
+    /// across the surface, solving the bi-Laplacian with Gauss-Seidel
+    fn fair(&mut self, patch: &[[usize; 3]], first: usize) {
+        let mut neighbours: Vec<HashSet<usize>> = vec![HashSet::new(); self.vertices.len()];
+        let faces = self
+            .faces
+            .iter()
+            .map(|f| f.positions())
+            .chain(patch.iter().copied());
+        for t in faces {
+            for i in 0..3 {
+                neighbours[t[i]].insert(t[(i + 1) % 3]);
+                neighbours[t[(i + 1) % 3]].insert(t[i]);
+            }
+        }
+
+        let neighbours: Vec<Vec<usize>> = neighbours
+            .into_iter()
+            .map(|n| {
+                let mut n: Vec<usize> = n.into_iter().collect();
+                n.sort_unstable();
+                n
+            })
+            .collect();
+
+        let umbrella = |vertices: &[Vec3], v: usize| {
+            let n = &neighbours[v];
+            n.iter().map(|&w| vertices[w]).sum::<Vec3>() / n.len().max(1) as f32 - vertices[v]
+        };
+
+        for _ in 0..FAIR_ITERATIONS {
+            for v in first..self.vertices.len() {
+                let n = &neighbours[v];
+                if n.is_empty() {
+                    continue;
+                }
+
+                let count = n.len() as f32;
+                let diagonal = 1.0
+                    + n.iter()
+                        .map(|&w| 1.0 / neighbours[w].len().max(1) as f32)
+                        .sum::<f32>()
+                        / count;
+                let laplacian = n.iter().map(|&w| umbrella(&self.vertices, w)).sum::<Vec3>()
+                    / count
+                    - umbrella(&self.vertices, v);
+                self.vertices[v] -= laplacian / diagonal;
+            }
+        }
+    }
+}
+
+/// Splits a loop at every vertex it passes through twice  
+/// returns the loops in between
+fn split_loop(boundary: Vec<usize>) -> Vec<Vec<usize>> {
+    let mut out = Vec::new();
+    let mut path: Vec<usize> = Vec::with_capacity(boundary.len());
+    for v in boundary {
+        if let Some(i) = path.iter().position(|&p| p == v) {
+            out.push(path.split_off(i));
+        }
+
+        path.push(v);
+    }
+
+    out.push(path);
+    out
+}
+
+/// returns the edges used by two triangles of `patch` and those triangles
+fn inner_edges(patch: &[[usize; 3]]) -> HashMap<(usize, usize), [usize; 2]> {
+    let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (f, t) in patch.iter().enumerate() {
+        for i in 0..3 {
+            edges.entry(key(t[i], t[(i + 1) % 3])).or_default().push(f);
+        }
+    }
+
+    edges
+        .into_iter()
+        .filter_map(|(edge, faces)| match faces[..] {
+            [f, g] => Some((edge, [f, g])),
+            _ => None,
+        })
+        .collect()
+}
+
+/// returns the corner of `triangle` that isn't `a` or `b`
+fn opposite(triangle: [usize; 3], a: usize, b: usize) -> usize {
+    triangle
+        .into_iter()
+        .find(|&v| v != a && v != b)
+        .unwrap_or(triangle[0])
+}
+
+/// Triangulates a loop of points with the least total area (Barequet–Sharir)
+fn minimum_area(points: &[Vec3]) -> Vec<[usize; 3]> {
+    let n = points.len();
+    let area = |i: usize, m: usize, j: usize| {
+        (points[m] - points[i])
+            .cross(points[j] - points[i])
+            .length()
+    };
+
+    let mut weight = vec![vec![0.0f32; n]; n];
+    let mut best = vec![vec![0; n]; n];
+    for span in 2..n {
+        for i in 0..n - span {
+            let j = i + span;
+            weight[i][j] = f32::INFINITY;
+            for m in i + 1..j {
+                let w = weight[i][m] + weight[m][j] + area(i, m, j);
+                if w < weight[i][j] {
+                    weight[i][j] = w;
+                    best[i][j] = m;
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::with_capacity(n - 2);
+    let mut stack = vec![(0, n - 1)];
+    while let Some((i, j)) = stack.pop() {
+        if j - i < 2 {
+            continue;
+        }
+
+        let m = best[i][j];
+        out.push([i, m, j]);
+        stack.extend([(i, m), (m, j)]);
+    }
+
+    out
+}
+
+/// Triangulates a loop of points flattened onto it's average plane
+fn ear_clipping(points: &[Vec3]) -> Vec<[usize; 3]> {
+    let center = points.iter().sum::<Vec3>() / points.len() as f32;
+    let normal = newell(points).normalize_or_zero();
+    if normal == Vec3::ZERO {
+        return minimum_area(points);
+    }
+
+    let plane = Plane::new(center, normal);
+    let outline: Vec<Vec2> = points.iter().map(|&p| plane.project(p)).collect();
+    triangulate(&outline, &[])
+}
+
+/// returns the area weighted normal of a polygon
+fn newell(points: &[Vec3]) -> Vec3 {
+    let mut normal = Vec3::ZERO;
+    for i in 0..points.len() {
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        normal += Vec3::new(
+            (a.y - b.y) * (a.z + b.z),
+            (a.z - b.z) * (a.x + b.x),
+            (a.x - b.x) * (a.y + b.y),
+        );
+    }
+
+    normal * 0.5
+}
+
+fn key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
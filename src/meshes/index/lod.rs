@@ -0,0 +1,141 @@
+use std::{collections::HashMap, ops::Range};
+
+use glam::Vec3;
+
+use crate::{bvh::Bvh, meshes::TriMesh, triangle::Triangle};
+
+use super::{Face, IndexError, IndexMesh, SimplifyOptions, VertexBuffer};
+
+/// A level of detail of a mesh
+#[derive(Clone, Debug, Default)]
+pub struct Lod {
+    pub faces: Vec<Face>,
+    /// the furthest distance between this level and the source mesh
+    pub error: f32,
+}
+
+/// Levels of detail sharing the vertices, uvs and normals of one mesh
+#[derive(Clone, Debug, Default)]
+pub struct LodChain {
+    /// the source mesh
+    pub mesh: IndexMesh,
+    pub levels: Vec<Lod>,
+}
+
+impl IndexMesh {
+    /// Creates a level of detail for every ratio of triangles in `ratios`
+    /// e.g. `[1.0, 0.5, 0.25, 0.125]`
+    ///
+    /// each level is simplified from the previous one,
+    /// so `ratios` should be decreasing  
+    /// `options.target_triangles` is replaced by each ratio,
+    /// levels keep more triangles when the other options stop simplification
+    pub fn lods(&self, ratios: &[f32], options: SimplifyOptions) -> LodChain {
+        let source = Bvh::new(&self.surface(&self.faces));
+        let samples = self.samples(&self.faces);
+
+        let mut current = self.clone();
+        let mut levels = Vec::with_capacity(ratios.len());
+        for &ratio in ratios {
+            let target_triangles = (self.faces.len() as f32 * ratio).round() as usize;
+            current = current.simplify(SimplifyOptions {
+                target_triangles,
+                ..options
+            });
+
+            let level = Bvh::new(&self.surface(&current.faces));
+            let error =
+                distance(&samples, &level).max(distance(&self.samples(&current.faces), &source));
+            levels.push(Lod {
+                faces: current.faces.clone(),
+                error,
+            });
+        }
+
+        LodChain {
+            mesh: self.clone(),
+            levels,
+        }
+    }
+
+    fn surface(&self, faces: &[Face]) -> TriMesh {
+        let triangles = faces
+            .iter()
+            .map(|f| Triangle::from_points(f.positions().map(|p| self.vertices[p])))
+            .collect();
+
+        TriMesh::new(triangles)
+    }
+
+    /// returns the corners, edge midpoints and centers of `faces`
+    fn samples(&self, faces: &[Face]) -> Vec<Vec3> {
+        let mut out = Vec::with_capacity(faces.len() * 7);
+        for face in faces {
+            let [a, b, c] = face.positions().map(|p| self.vertices[p]);
+            out.extend([
+                a,
+                b,
+                c,
+                (a + b) * 0.5,
+                (b + c) * 0.5,
+                (c + a) * 0.5,
+                (a + b + c) / 3.0,
+            ]);
+        }
+
+        out
+    }
+}
+
+impl LodChain {
+    /// returns the level at `index` as a mesh
+    pub fn level(&self, index: usize) -> IndexMesh {
+        let mut mesh = IndexMesh::new(
+            self.mesh.vertices.clone(),
+            self.mesh.normals.clone(),
+            self.mesh.uvs.clone(),
+            self.levels[index].faces.clone(),
+        );
+        mesh.tangents = self.mesh.tangents.clone();
+        mesh.attributes = self.mesh.attributes.clone();
+        mesh
+    }
+
+    /// Creates one `VertexBuffer` holding every level  
+    /// returns it with the range of indices for each level
+    ///
+    /// use `u16` or `u32` for `I`,
+    /// returns an error if there's a vertex `I` can't index
+    pub fn vertex_buffer<I: TryFrom<usize>>(
+        &self,
+    ) -> Result<(VertexBuffer<I>, Vec<Range<usize>>), IndexError> {
+        self.mesh.check_indices()?;
+
+        let mut out = VertexBuffer {
+            vertices: Vec::new(),
+            tangents: Vec::new(),
+            indices: Vec::new(),
+        };
+        let mut ranges = Vec::with_capacity(self.levels.len());
+        let mut corners = HashMap::new();
+        for level in &self.levels {
+            let start = out.indices.len();
+            self.mesh
+                .extend_buffer(&mut out, &level.faces, &mut corners)?;
+            ranges.push(start..out.indices.len());
+        }
+
+        Ok((out, ranges))
+    }
+}
+
+/// returns the furthest distance from any of `points` to `bvh`
+fn distance(points: &[Vec3], bvh: &Bvh) -> f32 {
+    points
+        .iter()
+        .map(|&p| match bvh.closest_point(p) {
+            Some((_, closest)) => closest.distance(p),
+            None => f32::INFINITY,
+        })
+        .fold(0.0, f32::max)
+}
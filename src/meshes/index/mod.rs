@@ -1,38 +1,113 @@
+mod buffer;
+mod error;
+mod face;
+mod holes;
+mod lod;
 mod modifiers;
+mod normals;
+mod orient;
+mod repair;
+mod simplify;
+mod subdivide;
+mod tangents;
 
-use glam::{Vec2, Vec3};
+use std::ops::{Index, IndexMut};
+
+use glam::{Vec2, Vec3, Vec4};
 
 use super::TriMesh;
+use crate::{
+    attribute::{Attributes, Source},
+    triangle::Triangle,
+    weld::Welder,
+};
+
+pub use buffer::VertexBuffer;
+pub use error::{Attribute, IndexError};
+pub use face::{Corner, Face};
+pub use holes::{FillMethod, FillOptions};
+pub use lod::{Lod, LodChain};
+pub use normals::{NormalOptions, NormalWeighting};
+pub use orient::OrientMethod;
+pub use repair::{RepairChange, RepairOptions};
+pub use simplify::SimplifyOptions;
+pub use subdivide::LoopOptions;
 
-/// TODO: Documentation
-/// Currently assumes indices are in triangles (0, 1, 2)
+/// A triangle mesh with indexed vertices, uvs and normals
+///
+/// every `Face` is a triangle made of three `Corner`s,
+/// each corner indexes into `vertices`, `uvs` and `normals` separately
 #[derive(Clone, Default, Debug)]
 pub struct IndexMesh {
     pub vertices: Vec<Vec3>,
     pub normals: Vec<Vec3>,
     pub uvs: Vec<Vec2>,
-    pub indices: Vec<[usize; 3]>,
+    pub faces: Vec<Face>,
+    /// tangents in xyz with the bitangent sign in w,
+    /// see `compute_tangents`
+    pub tangents: Vec<Vec4>,
+    /// custom values, corners index into every channel
+    pub attributes: Attributes,
 }
 
 impl IndexMesh {
-    pub fn new(
-        vertices: Vec<Vec3>,
-        normals: Vec<Vec3>,
-        uvs: Vec<Vec2>,
-        indices: Vec<[usize; 3]>,
-    ) -> Self {
+    pub fn new(vertices: Vec<Vec3>, normals: Vec<Vec3>, uvs: Vec<Vec2>, faces: Vec<Face>) -> Self {
         Self {
             vertices,
             normals,
             uvs,
-            indices,
+            faces,
+            tangents: Vec::new(),
+            attributes: Attributes::default(),
+        }
+    }
+
+    /// Creates a mesh from `mesh`
+    /// merging vertices within `tolerance` of each other
+    ///
+    /// uvs and normals are only merged when they're equal,
+    /// attributes are kept for every corner
+    pub fn from_trimesh(mesh: &TriMesh, tolerance: f32) -> Self {
+        let mut vertices = Welder::new(tolerance);
+        let mut uvs = Welder::new(0.0);
+        let mut normals = Welder::new(0.0);
+
+        let mut faces = Vec::with_capacity(mesh.tricount());
+        for tri in &mesh.triangles {
+            let n = normals.insert(tri.normal);
+            let corners = [0, 1, 2].map(|i| {
+                let v = vertices.insert(tri[i]);
+                let uv = uvs.insert(tri.uvs[i].extend(0.0));
+                Corner {
+                    attribute: (!mesh.attributes.is_empty()).then_some(faces.len() * 3 + i),
+                    ..Corner::new(v, uv, n)
+                }
+            });
+
+            faces.push(Face {
+                material: tri.material,
+                ..Face::new(corners)
+            });
         }
+
+        let mut out = Self::new(
+            vertices.into_points(),
+            normals.into_points(),
+            uvs.into_points()
+                .into_iter()
+                .map(|uv| uv.truncate())
+                .collect(),
+            faces,
+        );
+        out.attributes = mesh.attributes.clone();
+        out
     }
 
     /// Adds a vertex to this mesh
     /// unless the vertex already exists
     ///
-    /// then returns it's index
+    /// then returns it's index  
+    /// this is a linear search, use `from_trimesh` or `weld` for bulk inserts
     pub fn add_vertex(&mut self, vertex: Vec3) -> usize {
         for i in 0..self.vertices.len() {
             let v = &self.vertices[i];
@@ -68,7 +143,7 @@ impl IndexMesh {
     /// Adds a uv to this mesh
     /// unless the uv already exists
     ///
-    /// ten returns it's index
+    /// then returns it's index
     pub fn add_uv(&mut self, uv: Vec2) -> usize {
         for i in 0..self.uvs.len() {
             let v = &self.uvs[i];
@@ -83,12 +158,111 @@ impl IndexMesh {
         self.uvs.len() - 1
     }
 
-    /// Adds three indices to this mesh
-    /// [vertex, uv, normal]
-    pub fn add_triangle(&mut self, i0: [usize; 3], i1: [usize; 3], i2: [usize; 3]) {
-        self.indices.push(i0);
-        self.indices.push(i1);
-        self.indices.push(i2);
+    /// Adds a triangle made of three corners to this mesh
+    pub fn add_triangle(&mut self, c0: Corner, c1: Corner, c2: Corner) {
+        self.faces.push(Face::new([c0, c1, c2]));
+    }
+
+    /// returns the amount of triangles this mesh has
+    pub fn facecount(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// returns the nth face as a `Triangle`
+    ///
+    /// the triangle's normal is the corner normal
+    /// if all corners share one, otherwise their average  
+    /// returns an error if a corner is out of range
+    ///
+    /// ### Panic
+    /// panics if there's no nth face
+    pub fn triangle(&self, index: usize) -> Result<Triangle, IndexError> {
+        let face = self.check_face(index)?;
+        let [a, b, c] = face.corners;
+
+        let normal = if a.normal == b.normal && a.normal == c.normal {
+            self.normals[a.normal]
+        } else {
+            (self.normals[a.normal] + self.normals[b.normal] + self.normals[c.normal]) / 3.0
+        };
+
+        Ok(Triangle {
+            material: face.material,
+            ..Triangle::new(
+                face.corners.map(|c| self.vertices[c.position]),
+                face.corners.map(|c| self.uvs[c.uv]),
+                normal,
+            )
+        })
+    }
+
+    /// returns an iterator over every face as a `Triangle`
+    pub fn triangles(&self) -> impl Iterator<Item = Result<Triangle, IndexError>> + '_ {
+        (0..self.faces.len()).map(|i| self.triangle(i))
+    }
+
+    /// returns the attribute values of every face corner in order,
+    /// like a `TriMesh` holds them
+    ///
+    /// ### Panic
+    /// panics if a corner's attribute is out of range
+    pub(crate) fn corner_attributes(&self) -> Attributes {
+        let sources: Vec<Source> = self
+            .faces
+            .iter()
+            .flat_map(|f| f.corners)
+            .map(|c| c.attribute.map_or(Source::Default, Source::Copy))
+            .collect();
+
+        self.attributes.gather(&sources)
+    }
+
+    /// Checks that every corner indexes an existing vertex, uv, normal, tangent and attribute  
+    /// returns the first out of range index
+    pub fn check_indices(&self) -> Result<(), IndexError> {
+        for i in 0..self.faces.len() {
+            self.check_face(i)?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn check_face(&self, index: usize) -> Result<&Face, IndexError> {
+        let face = &self.faces[index];
+        for (corner, c) in face.corners.iter().enumerate() {
+            for (attribute, i, len) in [
+                (Attribute::Position, c.position, self.vertices.len()),
+                (Attribute::Uv, c.uv, self.uvs.len()),
+                (Attribute::Normal, c.normal, self.normals.len()),
+            ] {
+                if i >= len {
+                    return Err(IndexError::OutOfRange {
+                        face: index,
+                        corner,
+                        attribute,
+                        index: i,
+                        len,
+                    });
+                }
+            }
+
+            for (attribute, i, len) in [
+                (Attribute::Tangent, c.tangent, self.tangents.len()),
+                (Attribute::Custom, c.attribute, self.attributes.len()),
+            ] {
+                if let Some(i) = i.filter(|&i| i >= len) {
+                    return Err(IndexError::OutOfRange {
+                        face: index,
+                        corner,
+                        attribute,
+                        index: i,
+                        len,
+                    });
+                }
+            }
+        }
+
+        Ok(face)
     }
 
     /// returns a point with the maximum x, y and z values
@@ -112,23 +286,25 @@ impl IndexMesh {
     }
 }
 
+impl Index<usize> for IndexMesh {
+    type Output = Face;
+
+    /// returns a reference to the nth face
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.faces[index]
+    }
+}
+
+impl IndexMut<usize> for IndexMesh {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.faces[index]
+    }
+}
+
 impl From<TriMesh> for IndexMesh {
-    /// Takes this mesh and returns a `TriMesh`
+    /// Takes a `TriMesh` and returns this mesh  
+    /// only merges exactly equal vertices, uvs and normals
     fn from(value: TriMesh) -> Self {
-        let mut out = Self::default();
-        for i in 0..value.tricount() {
-            let tri = value[i];
-            let v0 = out.add_vertex(tri[0]);
-            let v1 = out.add_vertex(tri[1]);
-            let v2 = out.add_vertex(tri[2]);
-            let uv0 = out.add_uv(tri.uvs[0]);
-            let uv1 = out.add_uv(tri.uvs[1]);
-            let uv2 = out.add_uv(tri.uvs[2]);
-            let n = out.add_normal(tri.normal);
-
-            out.add_triangle([v0, uv0, n], [v1, uv1, n], [v2, uv2, n]);
-        }
-
-        out
+        Self::from_trimesh(&value, 0.0)
     }
 }
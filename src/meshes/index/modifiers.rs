@@ -1,8 +1,192 @@
-use super::IndexMesh;
+use std::collections::{BTreeMap, HashMap};
+
+use glam::Vec3;
+
+use crate::{
+    attribute::Source,
+    disjoint::DisjointSet,
+    meshes::{
+        separate::{loose_parts, near_points},
+        SeparateOptions,
+    },
+    weld::Welder,
+};
+
+use super::{Corner, Face, IndexError, IndexMesh};
 
 impl IndexMesh {
-    /// Seperates any loose parts into there own mesh
-    pub fn separate_by_loose_parts(self) -> Vec<Self> {
-        todo!();
+    /// Merges vertices within `tolerance` of each other
+    /// and rewrites the indices to use them
+    ///
+    /// uvs and normals are only merged when they're equal  
+    /// returns an error without changing the mesh if any index is out of range
+    pub fn weld(&mut self, tolerance: f32) -> Result<(), IndexError> {
+        self.check_indices()?;
+
+        let vertices = weld(self.vertices.iter().copied(), tolerance);
+        let uvs = weld(self.uvs.iter().map(|uv| uv.extend(0.0)), 0.0);
+        let normals = weld(self.normals.iter().copied(), 0.0);
+
+        for corner in self.faces.iter_mut().flat_map(|f| &mut f.corners) {
+            corner.position = vertices.0[corner.position];
+            corner.uv = uvs.0[corner.uv];
+            corner.normal = normals.0[corner.normal];
+        }
+
+        self.vertices = vertices.1;
+        self.uvs = uvs.1.into_iter().map(|uv| uv.truncate()).collect();
+        self.normals = normals.1;
+
+        Ok(())
+    }
+
+    /// Separates any loose parts into their own mesh
+    ///
+    /// triangles are connected when they share a vertex index
+    pub fn separate_by_loose_parts(self) -> Result<Vec<Self>, IndexError> {
+        self.separate_by_loose_parts_with(SeparateOptions::default())
+    }
+
+    /// Separates any loose parts into their own mesh
+    ///
+    /// each part only keeps the vertices, uvs, normals, tangents and attributes it uses  
+    /// returns an error if any index is out of range
+    pub fn separate_by_loose_parts_with(
+        self,
+        options: SeparateOptions,
+    ) -> Result<Vec<Self>, IndexError> {
+        self.check_indices()?;
+
+        let set = match options.tolerance {
+            Some(tolerance) => near_points(&self.vertices, tolerance),
+            None => DisjointSet::new(self.vertices.len()),
+        };
+
+        let triangles: Vec<[usize; 3]> = self.faces.iter().map(|f| f.positions()).collect();
+
+        Ok(loose_parts(&triangles, set, options.connectivity)
+            .into_iter()
+            .map(|part| self.extract(part))
+            .collect())
+    }
+
+    /// Splits this mesh into a mesh for every material
+    ///
+    /// returns the meshes along with their material, ordered by material  
+    /// each mesh keeps the order of its faces
+    /// and only the vertices, uvs, normals, tangents and attributes it uses
+    pub fn split_by_material(self) -> Vec<(u32, Self)> {
+        let mut materials: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+        for (f, face) in self.faces.iter().enumerate() {
+            materials.entry(face.material).or_default().push(f);
+        }
+
+        materials
+            .into_iter()
+            .map(|(material, part)| (material, self.extract(part)))
+            .collect()
+    }
+
+    /// Combines `meshes` into one mesh, in order
+    ///
+    /// the vertices, uvs, normals, tangents and attributes
+    /// of every mesh are kept apart, `weld` joins the vertices  
+    /// attribute channels missing from some meshes get default values there
+    pub fn merge(meshes: impl IntoIterator<Item = Self>) -> Self {
+        let mut out = Self::default();
+        for mesh in meshes {
+            let (v, uv, n, t) = (
+                out.vertices.len(),
+                out.uvs.len(),
+                out.normals.len(),
+                out.tangents.len(),
+            );
+            let rows = out.attributes.len();
+            out.attributes
+                .append(rows, &mesh.attributes, mesh.attributes.len());
+            out.faces.extend(mesh.faces.into_iter().map(|mut face| {
+                for c in &mut face.corners {
+                    c.position += v;
+                    c.uv += uv;
+                    c.normal += n;
+                    c.tangent = c.tangent.map(|i| i + t);
+                    c.attribute = c.attribute.map(|i| i + rows);
+                }
+
+                face
+            }));
+
+            out.vertices.extend(mesh.vertices);
+            out.uvs.extend(mesh.uvs);
+            out.normals.extend(mesh.normals);
+            out.tangents.extend(mesh.tangents);
+        }
+
+        out
+    }
+
+    /// returns a mesh of the faces in `part`
+    /// with only the vertices, uvs, normals, tangents and attributes they use
+    fn extract(&self, part: Vec<usize>) -> Self {
+        let mut mesh = Self::default();
+        let mut vertices = HashMap::new();
+        let mut uvs = HashMap::new();
+        let mut normals = HashMap::new();
+        let mut tangents = HashMap::new();
+        let mut rows = HashMap::new();
+        let mut sources = Vec::new();
+
+        for t in part {
+            let corners = self.faces[t].corners.map(|c| {
+                let v = *vertices.entry(c.position).or_insert_with(|| {
+                    mesh.vertices.push(self.vertices[c.position]);
+                    mesh.vertices.len() - 1
+                });
+                let uv = *uvs.entry(c.uv).or_insert_with(|| {
+                    mesh.uvs.push(self.uvs[c.uv]);
+                    mesh.uvs.len() - 1
+                });
+                let n = *normals.entry(c.normal).or_insert_with(|| {
+                    mesh.normals.push(self.normals[c.normal]);
+                    mesh.normals.len() - 1
+                });
+
+                let tangent = c.tangent.map(|t| {
+                    *tangents.entry(t).or_insert_with(|| {
+                        mesh.tangents.push(self.tangents[t]);
+                        mesh.tangents.len() - 1
+                    })
+                });
+
+                let attribute = c.attribute.map(|a| {
+                    *rows.entry(a).or_insert_with(|| {
+                        sources.push(Source::Copy(a));
+                        sources.len() - 1
+                    })
+                });
+
+                Corner {
+                    tangent,
+                    attribute,
+                    ..Corner::new(v, uv, n)
+                }
+            });
+
+            mesh.faces.push(Face {
+                material: self.faces[t].material,
+                ..Face::new(corners)
+            });
+        }
+
+        mesh.attributes = self.attributes.gather(&sources);
+        mesh
     }
 }
+
+/// Welds `points` and returns the new index of every point
+/// along with the welded points
+fn weld(points: impl Iterator<Item = Vec3>, tolerance: f32) -> (Vec<usize>, Vec<Vec3>) {
+    let mut welder = Welder::new(tolerance);
+    let remap = points.map(|p| welder.insert(p)).collect();
+    (remap, welder.into_points())
+}
@@ -0,0 +1,115 @@
+use std::collections::{HashMap, HashSet};
+
+use glam::Vec3;
+
+use crate::disjoint::DisjointSet;
+
+use super::IndexMesh;
+
+/// How much every face adds to the normals of its corners
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NormalWeighting {
+    /// by the angle of the face at the corner,
+    /// doesn't depend on how the surface is triangulated
+    #[default]
+    Angle,
+    /// by the area of the face
+    Area,
+    /// every face the same
+    Uniform,
+}
+
+/// Options for `IndexMesh::compute_normals`
+#[derive(Clone, Debug, Default)]
+pub struct NormalOptions {
+    pub weighting: NormalWeighting,
+    /// edges whose faces meet at a larger angle, in radians, are hard
+    pub crease_angle: Option<f32>,
+    /// edges between these vertices are hard
+    pub hard_edges: Vec<[usize; 2]>,
+}
+
+impl IndexMesh {
+    /// Replaces the normals with smooth normals from the faces' winding
+    ///
+    /// corners of a vertex share a normal when their faces
+    /// are connected around it by smooth edges,  
+    /// hard edges, open boundaries and edges used by more than 2 faces
+    /// split the normals
+    ///
+    /// ### Panic
+    /// panics if a position is out of range
+    pub fn compute_normals(&mut self, options: &NormalOptions) {
+        let hard: HashSet<(usize, usize)> = options
+            .hard_edges
+            .iter()
+            .map(|&[a, b]| (a.min(b), a.max(b)))
+            .collect();
+
+        let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (f, face) in self.faces.iter().enumerate() {
+            let p = face.positions();
+            for i in 0..3 {
+                let (a, b) = (p[i], p[(i + 1) % 3]);
+                edges.entry((a.min(b), a.max(b))).or_default().push(f);
+            }
+        }
+
+        // corner `i` of face `f` is set `3 * f + i`
+        let normals: Vec<Vec3> = self.faces.iter().map(|f| self.face_normal(f)).collect();
+        let mut groups = DisjointSet::new(self.faces.len() * 3);
+        for (edge, faces) in &edges {
+            let &[f, g] = &faces[..] else {
+                continue;
+            };
+
+            let crease = options
+                .crease_angle
+                .is_some_and(|angle| normals[f].angle_between(normals[g]) > angle);
+            if crease || hard.contains(edge) {
+                continue;
+            }
+
+            for v in [edge.0, edge.1] {
+                let corner = |f: usize| {
+                    let i = self.faces[f].positions().iter().position(|&p| p == v);
+                    3 * f + i.expect("the face uses the edge")
+                };
+
+                groups.union(corner(f), corner(g));
+            }
+        }
+
+        let mut sums: HashMap<usize, Vec3> = HashMap::new();
+        for (f, face) in self.faces.iter().enumerate() {
+            let p = face.positions().map(|p| self.vertices[p]);
+            for i in 0..3 {
+                let weight = match options.weighting {
+                    NormalWeighting::Angle => {
+                        let ab = p[(i + 1) % 3] - p[i];
+                        let ac = p[(i + 2) % 3] - p[i];
+                        ab.angle_between(ac)
+                    }
+                    NormalWeighting::Area => (p[1] - p[0]).cross(p[2] - p[0]).length() / 2.0,
+                    NormalWeighting::Uniform => 1.0,
+                };
+
+                // degenerate faces have no direction and add nothing
+                let weight = if weight.is_finite() { weight } else { 0.0 };
+                *sums.entry(groups.find(3 * f + i)).or_default() += normals[f] * weight;
+            }
+        }
+
+        let mut indices = HashMap::new();
+        self.normals.clear();
+        for f in 0..self.faces.len() {
+            for i in 0..3 {
+                let group = groups.find(3 * f + i);
+                self.faces[f][i].normal = *indices.entry(group).or_insert_with(|| {
+                    self.normals.push(sums[&group].normalize_or_zero());
+                    self.normals.len() - 1
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::{HashMap, VecDeque};
+
+use glam::Vec3;
+
+use crate::{bvh::Bvh, meshes::TriMesh, traits::Raycast, triangle::Triangle};
+
+use super::IndexMesh;
+
+/// How `IndexMesh::orient` finds out if a closed part faces inwards
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OrientMethod {
+    /// the part encloses a negative volume
+    #[default]
+    SignedVolume,
+    /// rays leaving the part's largest faces along their normals
+    /// mostly cross the mesh an odd number of times,
+    /// handles parts nested inside each other
+    RayParity,
+}
+
+/// the amount of faces cast from with `OrientMethod::RayParity`
+const PARITY_RAYS: usize = 3;
+
+impl IndexMesh {
+    /// Makes faces sharing an edge wind the same way as most of their part,
+    /// then flips every closed connected part facing inwards  
+    /// flipped faces get negated normals
+    ///
+    /// returns the flipped faces
+    pub fn orient(&mut self, method: OrientMethod) -> Vec<usize> {
+        let flipped = self.flips(method);
+        let mut negated = HashMap::new();
+        for &f in &flipped {
+            let face = &mut self.faces[f];
+            face.corners.swap(1, 2);
+            for corner in &mut face.corners {
+                corner.normal = *negated.entry(corner.normal).or_insert_with(|| {
+                    self.normals.push(-self.normals[corner.normal]);
+                    self.normals.len() - 1
+                });
+            }
+        }
+
+        flipped
+    }
+
+    /// returns the faces `orient` flips
+    pub(crate) fn flips(&self, method: OrientMethod) -> Vec<usize> {
+        let mut edges: HashMap<(usize, usize), Vec<(usize, bool)>> = HashMap::new();
+        for (f, face) in self.faces.iter().enumerate() {
+            let p = face.positions();
+            for i in 0..3 {
+                let (a, b) = (p[i], p[(i + 1) % 3]);
+                edges
+                    .entry((a.min(b), a.max(b)))
+                    .or_default()
+                    .push((f, a < b));
+            }
+        }
+
+        // whether every face is flipped, decided a connected part at a time
+        let mut flipped: Vec<Option<bool>> = vec![None; self.faces.len()];
+        let mut closed_parts = Vec::new();
+        for start in 0..self.faces.len() {
+            if flipped[start].is_some() {
+                continue;
+            }
+
+            flipped[start] = Some(false);
+            let mut part = vec![start];
+            let mut closed = true;
+            let mut queue = VecDeque::from([start]);
+            while let Some(f) = queue.pop_front() {
+                let p = self.faces[f].positions();
+                for i in 0..3 {
+                    let (a, b) = (p[i], p[(i + 1) % 3]);
+                    let forward = (a < b) != flipped[f].unwrap_or(false);
+                    let uses = &edges[&(a.min(b), a.max(b))];
+                    if uses.len() != 2 {
+                        closed = false;
+                        continue;
+                    }
+
+                    for &(g, g_forward) in uses {
+                        if flipped[g].is_none() {
+                            // neighbours should run along the edge the other way
+                            flipped[g] = Some(g_forward == forward);
+                            part.push(g);
+                            queue.push_back(g);
+                        }
+                    }
+                }
+            }
+
+            // keep the winding most of the part already has
+            if part.iter().filter(|&&f| flipped[f] == Some(true)).count() * 2 > part.len() {
+                for &f in &part {
+                    flipped[f] = flipped[f].map(|flip| !flip);
+                }
+            }
+
+            if closed {
+                closed_parts.push(part);
+            }
+        }
+
+        let triangle = |f: usize| {
+            let mut p = self.faces[f].positions().map(|p| self.vertices[p]);
+            if flipped[f] == Some(true) {
+                p.swap(1, 2);
+            }
+
+            Triangle::from_points(p)
+        };
+
+        // every part is tested against the whole mesh to see through nesting
+        let bvh = (method == OrientMethod::RayParity).then(|| {
+            Bvh::new(&TriMesh::new(
+                (0..self.faces.len()).map(&triangle).collect(),
+            ))
+        });
+
+        let mut inwards = Vec::new();
+        for part in &closed_parts {
+            let triangles: Vec<Triangle> = part.iter().map(|&f| triangle(f)).collect();
+            inwards.push(match &bvh {
+                None => TriMesh::new(triangles).volume() < 0.0,
+                Some(bvh) => {
+                    let mut largest: Vec<&Triangle> = triangles.iter().collect();
+                    largest.sort_by(|a, b| b.area().total_cmp(&a.area()));
+                    largest.truncate(PARITY_RAYS);
+
+                    let odd = largest
+                        .iter()
+                        .filter(|t| {
+                            let center = (t[0] + t[1] + t[2]) / 3.0;
+                            crossings(bvh, center, t.normal.normalize_or_zero()) % 2 == 1
+                        })
+                        .count();
+                    odd * 2 > largest.len()
+                }
+            });
+        }
+
+        for (part, inwards) in closed_parts.iter().zip(inwards) {
+            if inwards {
+                for &f in part {
+                    flipped[f] = flipped[f].map(|flip| !flip);
+                }
+            }
+        }
+
+        (0..self.faces.len())
+            .filter(|&f| flipped[f] == Some(true))
+            .collect()
+    }
+}
+
+/// returns how many triangles a ray from `origin` along `direction` crosses
+fn crossings(bvh: &Bvh, origin: Vec3, direction: Vec3) -> usize {
+    // skip the face the ray starts on
+    let epsilon = 1e-4;
+    let ray = (origin, direction);
+    let mut count = 0;
+    let mut t = epsilon;
+    while let Some(hit) = bvh.raycast_range(&ray, t, f32::INFINITY) {
+        count += 1;
+        t = hit.distance + epsilon;
+    }
+
+    count
+}
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+
+use crate::meshes::validate::{fans, is_degenerate};
+
+use super::{FillOptions, IndexMesh, OrientMethod};
+
+/// Options for `IndexMesh::repair`,
+/// every step is enabled by default
+#[derive(Clone, Copy, Debug)]
+pub struct RepairOptions {
+    /// merges vertices within this distance, None to skip welding
+    pub weld_tolerance: Option<f32>,
+    pub remove_degenerate: bool,
+    pub remove_duplicates: bool,
+    /// gives every fan of faces around a non manifold vertex it's own copy
+    pub split_non_manifold_vertices: bool,
+    /// makes the winding consistent and closed parts face outwards,
+    /// None to keep the winding
+    pub orient: Option<OrientMethod>,
+    /// fills holes, None to leave them open
+    pub fill_holes: Option<FillOptions>,
+}
+
+impl Default for RepairOptions {
+    fn default() -> Self {
+        Self {
+            weld_tolerance: Some(0.0),
+            remove_degenerate: true,
+            remove_duplicates: true,
+            split_non_manifold_vertices: true,
+            orient: Some(OrientMethod::SignedVolume),
+            fill_holes: Some(FillOptions {
+                max_edges: Some(16),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+/// A change made by `IndexMesh::repair`  
+/// indices refer to the mesh as it was right before the change
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RepairChange {
+    /// faces with a corner out of range
+    RemovedOutOfRange {
+        faces: Vec<usize>,
+    },
+    /// this many vertices were merged into others
+    Welded {
+        merged: usize,
+    },
+    RemovedDegenerate {
+        faces: Vec<usize>,
+    },
+    RemovedDuplicates {
+        faces: Vec<usize>,
+    },
+    /// a non manifold vertex was copied to the new `vertices`
+    SplitVertex {
+        vertex: usize,
+        copies: Vec<usize>,
+    },
+    FlippedFaces {
+        faces: Vec<usize>,
+    },
+    /// a hole around `vertices` was filled with `faces` new faces
+    FilledHole {
+        vertices: Vec<usize>,
+        faces: usize,
+    },
+}
+
+impl IndexMesh {
+    /// Fixes the problems `analyze` finds, as far as `options` allow,
+    /// in this order: welding, degenerate faces, duplicate faces,
+    /// non manifold vertices, winding, then holes
+    ///
+    /// returns the repaired mesh and every change made to it  
+    /// faces with a corner out of range are always removed first,
+    /// non manifold edges are left alone
+    pub fn repair(&self, options: RepairOptions) -> (IndexMesh, Vec<RepairChange>) {
+        let mut mesh = self.clone();
+        let mut log = Vec::new();
+
+        let faces = mesh.remove_faces(|mesh, f| mesh.check_face(f).is_err());
+        if !faces.is_empty() {
+            log.push(RepairChange::RemovedOutOfRange { faces });
+        }
+
+        if let Some(tolerance) = options.weld_tolerance {
+            let before = mesh.vertices.len();
+            mesh.weld(tolerance)
+                .expect("out of range faces were removed");
+            if mesh.vertices.len() < before {
+                log.push(RepairChange::Welded {
+                    merged: before - mesh.vertices.len(),
+                });
+            }
+        }
+
+        if options.remove_degenerate {
+            let faces = mesh.remove_faces(|mesh, f| {
+                is_degenerate(mesh.faces[f].positions().map(|p| mesh.vertices[p]))
+            });
+            if !faces.is_empty() {
+                log.push(RepairChange::RemovedDegenerate { faces });
+            }
+        }
+
+        if options.remove_duplicates {
+            let mut seen = HashSet::new();
+            let faces = mesh.remove_faces(|mesh, f| {
+                let mut sorted = mesh.faces[f].positions();
+                sorted.sort_unstable();
+                !seen.insert(sorted)
+            });
+            if !faces.is_empty() {
+                log.push(RepairChange::RemovedDuplicates { faces });
+            }
+        }
+
+        if options.split_non_manifold_vertices {
+            log.extend(mesh.split_non_manifold_vertices());
+        }
+
+        if let Some(method) = options.orient {
+            let faces = mesh.orient(method);
+            if !faces.is_empty() {
+                log.push(RepairChange::FlippedFaces { faces });
+            }
+        }
+
+        if let Some(fill) = options.fill_holes {
+            for vertices in mesh.hole_loops() {
+                let faces = mesh.fill_hole(&vertices, fill);
+                if faces > 0 {
+                    log.push(RepairChange::FilledHole { vertices, faces });
+                }
+            }
+        }
+
+        (mesh, log)
+    }
+
+    /// Removes every face `remove` returns true for, in order  
+    /// returns the removed faces
+    fn remove_faces(&mut self, mut remove: impl FnMut(&Self, usize) -> bool) -> Vec<usize> {
+        let removed: Vec<usize> = (0..self.faces.len()).filter(|&f| remove(self, f)).collect();
+        let mut i = 0;
+        self.faces.retain(|_| {
+            i += 1;
+            removed.binary_search(&(i - 1)).is_err()
+        });
+
+        removed
+    }
+
+    /// Copies vertices used by more than one fan of faces,
+    /// so every fan gets it's own
+    fn split_non_manifold_vertices(&mut self) -> Vec<RepairChange> {
+        let positions: Vec<[usize; 3]> = self.faces.iter().map(|f| f.positions()).collect();
+        let fans = fans(&positions);
+
+        // the fans found so far around every vertex
+        let mut seen: Vec<Vec<usize>> = vec![Vec::new(); self.vertices.len()];
+        let mut copies: Vec<Vec<usize>> = vec![Vec::new(); self.vertices.len()];
+        for (c, &fan) in fans.iter().enumerate() {
+            let corner = &mut self.faces[c / 3].corners[c % 3];
+            let v = corner.position;
+            let index = match seen[v].iter().position(|&f| f == fan) {
+                Some(index) => index,
+                None => {
+                    seen[v].push(fan);
+                    if seen[v].len() > 1 {
+                        copies[v].push(self.vertices.len());
+                        self.vertices.push(self.vertices[v]);
+                    }
+
+                    seen[v].len() - 1
+                }
+            };
+
+            if index > 0 {
+                corner.position = copies[v][index - 1];
+            }
+        }
+
+        copies
+            .into_iter()
+            .enumerate()
+            .filter(|(_, copies)| !copies.is_empty())
+            .map(|(vertex, copies)| RepairChange::SplitVertex { vertex, copies })
+            .collect()
+    }
+}
@@ -0,0 +1,427 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use glam::{DVec3, Vec3};
+
+use super::{Corner, IndexMesh};
+
+/// how much more boundary edges resist moving than surfaces
+const BOUNDARY_WEIGHT: f64 = 100.0;
+
+/// Options for `IndexMesh::simplify`
+#[derive(Clone, Copy, Debug)]
+pub struct SimplifyOptions {
+    /// stop once the mesh has this many triangles
+    pub target_triangles: usize,
+    /// the furthest a vertex may move from the planes of the original faces
+    /// it took the place of, and of their open boundaries  
+    /// the quadric error only orders the collapses,
+    /// this is a distance in the mesh's units
+    pub max_error: f32,
+    /// keep vertices on open boundaries from moving
+    pub lock_boundary: bool,
+}
+
+impl Default for SimplifyOptions {
+    fn default() -> Self {
+        Self {
+            target_triangles: 0,
+            max_error: f32::INFINITY,
+            lock_boundary: false,
+        }
+    }
+}
+
+/// A symmetric 4x4 matrix measuring the squared distance to a set of planes
+#[derive(Clone, Copy, Debug, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    fn plane(normal: DVec3, point: DVec3, weight: f64) -> Self {
+        let [a, b, c] = normal.to_array();
+        let d = -normal.dot(point);
+        Self(
+            [
+                a * a,
+                a * b,
+                a * c,
+                a * d,
+                b * b,
+                b * c,
+                b * d,
+                c * c,
+                c * d,
+                d * d,
+            ]
+            .map(|q| q * weight),
+        )
+    }
+
+    fn add(&mut self, other: &Quadric) {
+        for i in 0..10 {
+            self.0[i] += other.0[i];
+        }
+    }
+
+    fn error(&self, p: DVec3) -> f64 {
+        let q = &self.0;
+        let [x, y, z] = p.to_array();
+        let e = q[0] * x * x
+            + 2.0 * q[1] * x * y
+            + 2.0 * q[2] * x * z
+            + 2.0 * q[3] * x
+            + q[4] * y * y
+            + 2.0 * q[5] * y * z
+            + 2.0 * q[6] * y
+            + q[7] * z * z
+            + 2.0 * q[8] * z
+            + q[9];
+        e.max(0.0)
+    }
+}
+
+/// An edge collapse moving `from` onto `to`
+#[derive(Clone, Copy, Debug)]
+struct Collapse {
+    error: f64,
+    from: usize,
+    to: usize,
+    versions: (u32, u32),
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    /// reversed so the heap pops the cheapest collapse first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.error.total_cmp(&self.error)
+    }
+}
+
+struct Simplifier<'a> {
+    mesh: &'a IndexMesh,
+    faces: Vec<[Corner; 3]>,
+    alive: Vec<bool>,
+    vertex_faces: Vec<Vec<usize>>,
+    quadrics: Vec<Quadric>,
+    /// the unit normal and offset of every original face and boundary plane
+    planes: Vec<(DVec3, f64)>,
+    /// the planes every vertex has taken the place of, sorted
+    vertex_planes: Vec<Vec<usize>>,
+    locked: Vec<bool>,
+    versions: Vec<u32>,
+    heap: BinaryHeap<Collapse>,
+}
+
+impl IndexMesh {
+    /// Simplifies this mesh by collapsing edges
+    /// in order of their quadric error (Garland–Heckbert)
+    ///
+    /// vertices only move onto their neighbours,
+    /// so the result shares this mesh's vertices, uvs, normals and attributes  
+    /// vertices on uv seams are never removed,
+    /// and collapses that flip a triangle are skipped
+    ///
+    /// ### Panic
+    /// panics if a corner is out of range
+    pub fn simplify(&self, options: SimplifyOptions) -> IndexMesh {
+        let mut simplifier = Simplifier::new(self, options.lock_boundary);
+        simplifier.run(options);
+
+        let mut out = self.clone();
+        out.faces = (0..simplifier.faces.len())
+            .filter(|&f| simplifier.alive[f])
+            .map(|f| {
+                let mut face = self.faces[f];
+                face.corners = simplifier.faces[f];
+                face
+            })
+            .collect();
+
+        out
+    }
+}
+
+impl<'a> Simplifier<'a> {
+    fn new(mesh: &'a IndexMesh, lock_boundary: bool) -> Self {
+        let n = mesh.vertices.len();
+        let faces: Vec<[Corner; 3]> = mesh.faces.iter().map(|f| f.corners).collect();
+        let mut vertex_faces = vec![Vec::new(); n];
+        let mut quadrics = vec![Quadric::default(); n];
+        let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        let mut uvs: Vec<Option<usize>> = vec![None; n];
+        let mut locked = vec![false; n];
+        let mut planes = Vec::new();
+        let mut vertex_planes = vec![Vec::new(); n];
+
+        for (f, corners) in faces.iter().enumerate() {
+            let p = corners.map(|c| mesh.vertices[c.position].as_dvec3());
+            let cross = (p[1] - p[0]).cross(p[2] - p[0]);
+            let normal = cross.normalize_or_zero();
+            let quadric = Quadric::plane(normal, p[0], cross.length() * 0.5);
+            planes.push((normal, -normal.dot(p[0])));
+
+            for (i, c) in corners.iter().enumerate() {
+                vertex_faces[c.position].push(f);
+                quadrics[c.position].add(&quadric);
+                vertex_planes[c.position].push(planes.len() - 1);
+
+                // vertices with more than one uv sit on a seam
+                match uvs[c.position] {
+                    Some(uv) if uv != c.uv => locked[c.position] = true,
+                    _ => uvs[c.position] = Some(c.uv),
+                }
+
+                let j = corners[(i + 1) % 3].position;
+                edges.entry(key(c.position, j)).or_default().push(f);
+            }
+        }
+
+        // Keep boundaries in shape with planes perpendicular to their faces
+        for (&(a, b), edge_faces) in &edges {
+            if edge_faces.len() != 1 {
+                continue;
+            }
+
+            if lock_boundary {
+                locked[a] = true;
+                locked[b] = true;
+            }
+
+            let p = faces[edge_faces[0]].map(|c| mesh.vertices[c.position].as_dvec3());
+            let normal = (p[1] - p[0]).cross(p[2] - p[0]).normalize_or_zero();
+            let (pa, pb) = (mesh.vertices[a].as_dvec3(), mesh.vertices[b].as_dvec3());
+            let perpendicular = (pb - pa).cross(normal).normalize_or_zero();
+            let quadric =
+                Quadric::plane(perpendicular, pa, BOUNDARY_WEIGHT * pa.distance_squared(pb));
+            quadrics[a].add(&quadric);
+            quadrics[b].add(&quadric);
+            planes.push((perpendicular, -perpendicular.dot(pa)));
+            vertex_planes[a].push(planes.len() - 1);
+            vertex_planes[b].push(planes.len() - 1);
+        }
+
+        for list in &mut vertex_planes {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        let mut simplifier = Self {
+            mesh,
+            alive: vec![true; faces.len()],
+            faces,
+            vertex_faces,
+            quadrics,
+            planes,
+            vertex_planes,
+            locked,
+            versions: vec![0; n],
+            heap: BinaryHeap::new(),
+        };
+
+        let mut keys: Vec<(usize, usize)> = edges.into_keys().collect();
+        keys.sort_unstable();
+        for (a, b) in keys {
+            simplifier.push(a, b);
+        }
+
+        simplifier
+    }
+
+    fn run(&mut self, options: SimplifyOptions) {
+        let max_error = options.max_error as f64;
+        let mut count = self.faces.len();
+        while count > options.target_triangles {
+            let collapse = match self.heap.pop() {
+                Some(collapse) => collapse,
+                None => break,
+            };
+
+            if collapse.versions != (self.versions[collapse.from], self.versions[collapse.to]) {
+                continue;
+            }
+
+            // later collapses may still be close enough, the heap isn't ordered by distance
+            if max_error.is_finite() && self.distance(collapse.from, collapse.to) > max_error {
+                continue;
+            }
+
+            if let Some(removed) = self.collapse(collapse.from, collapse.to) {
+                count -= removed;
+            }
+        }
+    }
+
+    /// Queues the cheapest allowed direction to collapse an edge
+    fn push(&mut self, a: usize, b: usize) {
+        let mut quadric = self.quadrics[a];
+        quadric.add(&self.quadrics[b]);
+
+        let mut best: Option<(f64, usize, usize)> = None;
+        for (from, to) in [(a, b), (b, a)] {
+            if self.locked[from] {
+                continue;
+            }
+
+            let error = quadric.error(self.mesh.vertices[to].as_dvec3());
+            if matches!(best, Some(b) if b.0 <= error) {
+                continue;
+            }
+
+            best = Some((error, from, to));
+        }
+
+        if let Some((error, from, to)) = best {
+            self.heap.push(Collapse {
+                error,
+                from,
+                to,
+                versions: (self.versions[from], self.versions[to]),
+            });
+        }
+    }
+
+    /// Moves `from` onto `to`  
+    /// returns the amount of removed faces, or None if the collapse isn't allowed
+    fn collapse(&mut self, from: usize, to: usize) -> Option<usize> {
+        let faces: Vec<usize> = self.vertex_faces[from]
+            .iter()
+            .copied()
+            .filter(|&f| self.alive[f])
+            .collect();
+        let (shared, moved): (Vec<usize>, Vec<usize>) = faces
+            .into_iter()
+            .partition(|&f| self.faces[f].iter().any(|c| c.position == to));
+        if shared.is_empty() {
+            return None;
+        }
+
+        // Keep the mesh manifold, the ends may only share the vertices
+        // opposite to the collapsed edge
+        let from_ring = self.ring(from);
+        if from_ring.intersection(&self.ring(to)).count() > shared.len() {
+            return None;
+        }
+
+        // The moved corners take the attributes `to` has in the removed faces
+        let corners: Vec<Corner> = shared
+            .iter()
+            .map(|&f| *self.faces[f].iter().find(|c| c.position == to).unwrap())
+            .collect();
+        if corners.iter().any(|c| c.uv != corners[0].uv) {
+            return None;
+        }
+
+        let normal = corners
+            .iter()
+            .all(|c| c.normal == corners[0].normal)
+            .then_some(corners[0].normal);
+        let attribute = corners
+            .iter()
+            .all(|c| c.attribute == corners[0].attribute)
+            .then_some(corners[0].attribute);
+
+        let target = self.mesh.vertices[to];
+        for &f in &moved {
+            let before = self.normal(f, None);
+            let after = self.normal(f, Some((from, target)));
+            if before.dot(after) <= 0.0 || after == Vec3::ZERO {
+                return None;
+            }
+        }
+
+        for &f in &shared {
+            self.alive[f] = false;
+        }
+
+        for &f in &moved {
+            for c in &mut self.faces[f] {
+                if c.position == from {
+                    *c = Corner {
+                        attribute: attribute.unwrap_or(c.attribute),
+                        ..Corner::new(to, corners[0].uv, normal.unwrap_or(c.normal))
+                    };
+                }
+            }
+
+            self.vertex_faces[to].push(f);
+        }
+
+        let quadric = self.quadrics[from];
+        self.quadrics[to].add(&quadric);
+        let mut planes = std::mem::take(&mut self.vertex_planes[from]);
+        planes.append(&mut self.vertex_planes[to]);
+        planes.sort_unstable();
+        planes.dedup();
+        self.vertex_planes[to] = planes;
+        self.vertex_faces[from].clear();
+        self.versions[from] += 1;
+        self.versions[to] += 1;
+
+        self.vertex_faces[to].retain(|&f| self.alive[f]);
+
+        // Requeue the edges around `to`, their cost changed with its quadric
+        let mut ring: Vec<usize> = self.ring(to).into_iter().collect();
+        ring.sort_unstable();
+        for n in ring {
+            self.push(to, n);
+        }
+
+        Some(shared.len())
+    }
+
+    /// returns the furthest distance from `to`
+    /// to the planes `from` has taken the place of
+    fn distance(&self, from: usize, to: usize) -> f64 {
+        let p = self.mesh.vertices[to].as_dvec3();
+        self.vertex_planes[from]
+            .iter()
+            .map(|&i| {
+                let (normal, d) = self.planes[i];
+                (normal.dot(p) + d).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// returns the vertices sharing a face with `vertex`
+    fn ring(&self, vertex: usize) -> HashSet<usize> {
+        let mut out = HashSet::new();
+        for &f in &self.vertex_faces[vertex] {
+            if self.alive[f] {
+                out.extend(self.faces[f].iter().map(|c| c.position));
+            }
+        }
+
+        out.remove(&vertex);
+        out
+    }
+
+    /// returns the unit normal of a face,
+    /// optionally with a vertex moved
+    fn normal(&self, face: usize, moved: Option<(usize, Vec3)>) -> Vec3 {
+        let p = self.faces[face].map(|c| match moved {
+            Some((from, to)) if c.position == from => to,
+            _ => self.mesh.vertices[c.position],
+        });
+
+        (p[1] - p[0]).cross(p[2] - p[0]).normalize_or_zero()
+    }
+}
+
+fn key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
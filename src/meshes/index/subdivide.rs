@@ -0,0 +1,250 @@
+use std::{
+    collections::{HashMap, HashSet},
+    f32::consts::TAU,
+};
+
+use glam::Vec3;
+
+use crate::attribute::Source;
+
+use super::{Corner, Face, IndexMesh};
+
+/// Options for `IndexMesh::loop_subdivide`
+#[derive(Clone, Debug, Default)]
+pub struct LoopOptions {
+    /// edges between these vertices stay sharp
+    pub creases: Vec<[usize; 2]>,
+    /// edges whose faces meet at a larger angle, in radians, also stay sharp
+    pub crease_angle: Option<f32>,
+    /// when set, only triangles with an edge longer than this are subdivided
+    pub max_edge_length: Option<f32>,
+}
+
+impl IndexMesh {
+    /// Subdivides every triangle into 4 other triangles
+    /// and moves the vertices towards a smooth surface (Loop subdivision)
+    ///
+    /// open boundaries, creases and edges used by more than 2 faces stay sharp,
+    /// vertices on more than 2 of them don't move  
+    /// uvs, normals and attributes are interpolated linearly along edges,
+    /// so seams stay where they are  
+    /// tangents are dropped, use `compute_tangents` again
+    ///
+    /// in adaptive mode, triangles next to subdivided ones are split
+    /// so there are no cracks,
+    /// and only vertices surrounded by subdivided triangles move
+    ///
+    /// ### Panic
+    /// panics if a corner is out of range
+    pub fn loop_subdivide(&self, options: &LoopOptions) -> IndexMesh {
+        let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (f, face) in self.faces.iter().enumerate() {
+            for i in 0..3 {
+                edges
+                    .entry(key(face[i].position, face[(i + 1) % 3].position))
+                    .or_default()
+                    .push(f);
+            }
+        }
+
+        let normals: Vec<Vec3> = self.faces.iter().map(|f| self.face_normal(f)).collect();
+        let tagged: HashSet<(usize, usize)> =
+            options.creases.iter().map(|&[a, b]| key(a, b)).collect();
+        let sharp = |edge: &(usize, usize), faces: &Vec<usize>| match faces[..] {
+            [a, b] => {
+                tagged.contains(edge)
+                    || options
+                        .crease_angle
+                        .is_some_and(|angle| normals[a].angle_between(normals[b]) > angle)
+            }
+            _ => true,
+        };
+
+        let refined: Vec<bool> = self
+            .faces
+            .iter()
+            .map(|face| match options.max_edge_length {
+                Some(max) => (0..3).any(|i| {
+                    let [a, b] = [face[i].position, face[(i + 1) % 3].position];
+                    self.vertices[a].distance(self.vertices[b]) > max
+                }),
+                None => true,
+            })
+            .collect();
+
+        let mut split: Vec<(usize, usize)> = edges
+            .iter()
+            .filter(|(_, faces)| faces.iter().any(|&f| refined[f]))
+            .map(|(&edge, _)| edge)
+            .collect();
+        split.sort_unstable();
+
+        // Move the existing vertices using their neighbours
+        let mut neighbours = vec![Vec::new(); self.vertices.len()];
+        let mut creases = vec![Vec::new(); self.vertices.len()];
+        let mut fixed = vec![false; self.vertices.len()];
+        for (&(a, b), faces) in &edges {
+            neighbours[a].push(b);
+            neighbours[b].push(a);
+            if sharp(&(a, b), faces) {
+                creases[a].push(b);
+                creases[b].push(a);
+            }
+
+            if !faces.iter().all(|&f| refined[f]) {
+                fixed[a] = true;
+                fixed[b] = true;
+            }
+        }
+
+        let mut vertices: Vec<Vec3> = (0..self.vertices.len())
+            .map(|v| {
+                let p = self.vertices[v];
+                if fixed[v] || neighbours[v].is_empty() {
+                    return p;
+                }
+
+                match creases[v][..] {
+                    [] | [_] => {
+                        let n = neighbours[v].len() as f32;
+                        let beta = (0.625 - (0.375 + 0.25 * (TAU / n).cos()).powi(2)) / n;
+                        let sum: Vec3 = neighbours[v].iter().map(|&n| self.vertices[n]).sum();
+                        p * (1.0 - n * beta) + sum * beta
+                    }
+                    [a, b] => p * 0.75 + (self.vertices[a] + self.vertices[b]) * 0.125,
+                    _ => p,
+                }
+            })
+            .collect();
+
+        // Add a vertex on every split edge
+        let mut midpoints = HashMap::with_capacity(split.len());
+        for edge in split {
+            let faces = &edges[&edge];
+            let (a, b) = (self.vertices[edge.0], self.vertices[edge.1]);
+            let p = if sharp(&edge, faces) {
+                (a + b) * 0.5
+            } else {
+                let [c, d] = [faces[0], faces[1]].map(|f| self.vertices[self.opposite(f, edge)]);
+                (a + b) * 0.375 + (c + d) * 0.125
+            };
+
+            midpoints.insert(edge, vertices.len());
+            vertices.push(p);
+        }
+
+        let mut out = IndexMesh::new(vertices, self.normals.clone(), self.uvs.clone(), Vec::new());
+        let mut uvs = HashMap::new();
+        let mut normals = HashMap::new();
+        let mut rows: Vec<Source> = (0..self.attributes.len()).map(Source::Copy).collect();
+        let mut middle_rows = HashMap::new();
+        for face in &self.faces {
+            let mut middle = [None; 3];
+            for (i, m) in middle.iter_mut().enumerate() {
+                let (a, b) = (face[i], face[(i + 1) % 3]);
+                *m = midpoints
+                    .get(&key(a.position, b.position))
+                    .map(|&position| {
+                        let uv = midpoint(&mut out.uvs, &mut uvs, a.uv, b.uv, |a, b| (a + b) * 0.5);
+                        let normal = midpoint(
+                            &mut out.normals,
+                            &mut normals,
+                            a.normal,
+                            b.normal,
+                            |a, b| (a + b).normalize_or_zero(),
+                        );
+
+                        let attribute = match (a.attribute, b.attribute) {
+                            (Some(a), Some(b)) => {
+                                Some(*middle_rows.entry(key(a, b)).or_insert_with(|| {
+                                    rows.push(Source::Lerp(a, b, 0.5));
+                                    rows.len() - 1
+                                }))
+                            }
+                            _ => None,
+                        };
+
+                        Corner {
+                            attribute,
+                            ..Corner::new(position, uv, normal)
+                        }
+                    });
+            }
+
+            let corners = face.corners.map(|c| Corner { tangent: None, ..c });
+            let faces = split_face(corners, middle).into_iter();
+            out.faces.extend(faces.map(|f| Face {
+                material: face.material,
+                ..f
+            }));
+        }
+
+        out.attributes = self.attributes.gather(&rows);
+        out
+    }
+
+    /// returns the vertex of `face` that isn't on `edge`
+    fn opposite(&self, face: usize, edge: (usize, usize)) -> usize {
+        let face = &self.faces[face];
+        face.positions()
+            .into_iter()
+            .find(|&p| p != edge.0 && p != edge.1)
+            .unwrap_or(face[0].position)
+    }
+
+    pub(super) fn face_normal(&self, face: &Face) -> Vec3 {
+        let [a, b, c] = face.positions().map(|p| self.vertices[p]);
+        (b - a).cross(c - a).normalize_or_zero()
+    }
+}
+
+/// Splits a triangle at the corners in `middle`,
+/// `middle[i]` is on the edge from corner i to i + 1
+fn split_face(corners: [Corner; 3], middle: [Option<Corner>; 3]) -> Vec<Face> {
+    let count = middle.iter().flatten().count();
+    // rotate so the split edges come first
+    let r = (0..3)
+        .find(|&r| (0..count).all(|i| middle[(r + i) % 3].is_some()))
+        .unwrap_or(0);
+    let c = |i: usize| corners[(r + i) % 3];
+    let m = |i: usize| middle[(r + i) % 3].unwrap();
+
+    match count {
+        3 => vec![
+            Face::new([c(0), m(0), m(2)]),
+            Face::new([c(1), m(1), m(0)]),
+            Face::new([c(2), m(2), m(1)]),
+            Face::new([m(0), m(1), m(2)]),
+        ],
+        2 => vec![
+            Face::new([c(1), m(1), m(0)]),
+            Face::new([c(0), m(0), m(1)]),
+            Face::new([c(0), m(1), c(2)]),
+        ],
+        1 => vec![Face::new([c(0), m(0), c(2)]), Face::new([m(0), c(1), c(2)])],
+        _ => vec![Face::new(corners)],
+    }
+}
+
+/// returns the index of the value between `values[a]` and `values[b]`,
+/// adding it the first time
+fn midpoint<T: Copy>(
+    values: &mut Vec<T>,
+    added: &mut HashMap<(usize, usize), usize>,
+    a: usize,
+    b: usize,
+    mix: impl Fn(T, T) -> T,
+) -> usize {
+    if a == b {
+        return a;
+    }
+
+    *added.entry(key(a, b)).or_insert_with(|| {
+        values.push(mix(values[a], values[b]));
+        values.len() - 1
+    })
+}
+
+fn key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
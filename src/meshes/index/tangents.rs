@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use glam::{Vec3, Vec4};
+
+use super::{IndexError, IndexMesh};
+
+/// The tangent frame of a face from its uvs
+struct Frame {
+    /// the direction u grows in
+    s: Vec3,
+    preserves_orientation: bool,
+    /// the uvs give no direction, so the face joins the first group reaching it
+    group_with_any: bool,
+    /// two of the face's corners have the same position
+    degenerate: bool,
+}
+
+/// Faces around a vertex connected by edges
+/// with the same uv orientation
+struct Group {
+    preserves_orientation: bool,
+    /// the face and the index of the vertex's corner in it
+    corners: Vec<(usize, usize)>,
+}
+
+impl IndexMesh {
+    /// Generates a tangent for every corner from the uvs and normals
+    /// the same way MikkTSpace does,
+    /// so normal maps baked by other tools render the same
+    ///
+    /// corners are the same vertex when their position, uv and normal are equal  
+    /// the faces around a vertex that are connected by edges
+    /// and have the same uv orientation share a tangent,
+    /// their directions are weighted by the face angle at the vertex  
+    /// the bitangent is `w * normal.cross(tangent)`
+    ///
+    /// replaces `tangents`,
+    /// returns an error if a corner is out of range
+    pub fn compute_tangents(&mut self) -> Result<(), IndexError> {
+        self.check_indices()?;
+
+        // the vertices the reference works with
+        let mut ids = HashMap::new();
+        let vertex: Vec<usize> = self
+            .faces
+            .iter()
+            .flat_map(|f| f.corners)
+            .map(|c| {
+                let key: Vec<u32> = self.vertices[c.position]
+                    .to_array()
+                    .into_iter()
+                    .chain(self.normals[c.normal].to_array())
+                    .chain(self.uvs[c.uv].to_array())
+                    // adding zero makes -0.0 equal to 0.0
+                    .map(|x| (x + 0.0).to_bits())
+                    .collect();
+                let next = ids.len();
+                *ids.entry(key).or_insert(next)
+            })
+            .collect();
+
+        let mut frames: Vec<Frame> = (0..self.faces.len()).map(|f| self.frame(f)).collect();
+        for (frame, face) in frames.iter_mut().zip(&self.faces) {
+            let [a, b, c] = face.positions().map(|p| self.vertices[p]);
+            frame.degenerate = a == b || b == c || a == c;
+        }
+
+        let mut edges = HashMap::new();
+        for f in (0..self.faces.len()).filter(|&f| !frames[f].degenerate) {
+            for i in 0..3 {
+                edges
+                    .entry((vertex[3 * f + i], vertex[3 * f + (i + 1) % 3]))
+                    .or_insert(f);
+            }
+        }
+
+        // the face across the edge from corner i to i + 1
+        let neighbour = |f: usize, i: usize| {
+            let edge = (vertex[3 * f + (i + 1) % 3], vertex[3 * f + i]);
+            edges.get(&edge).copied().filter(|&g| g != f)
+        };
+
+        let mut assigned: Vec<Option<usize>> = vec![None; vertex.len()];
+        let mut groups: Vec<Group> = Vec::new();
+        for f in 0..self.faces.len() {
+            if frames[f].degenerate || frames[f].group_with_any {
+                continue;
+            }
+
+            for i in 0..3 {
+                if assigned[3 * f + i].is_some() {
+                    continue;
+                }
+
+                let g = groups.len();
+                let v = vertex[3 * f + i];
+                let orientation = frames[f].preserves_orientation;
+                let mut corners = Vec::new();
+
+                let mut stack = vec![f];
+                while let Some(f) = stack.pop() {
+                    let Some(i) = (0..3).find(|&i| vertex[3 * f + i] == v) else {
+                        continue;
+                    };
+
+                    if assigned[3 * f + i].is_some() {
+                        continue;
+                    }
+
+                    let frame = &mut frames[f];
+                    if frame.group_with_any && (0..3).all(|i| assigned[3 * f + i].is_none()) {
+                        frame.preserves_orientation = orientation;
+                    }
+
+                    if frame.preserves_orientation != orientation {
+                        continue;
+                    }
+
+                    assigned[3 * f + i] = Some(g);
+                    corners.push((f, i));
+                    stack.extend(neighbour(f, (i + 2) % 3));
+                    stack.extend(neighbour(f, i));
+                }
+
+                groups.push(Group {
+                    preserves_orientation: orientation,
+                    corners,
+                });
+            }
+        }
+
+        let tangents: Vec<Vec4> = groups
+            .iter()
+            .map(|g| self.group_tangent(g, &frames))
+            .collect();
+
+        // degenerate faces copy the tangent of their vertex from a proper face
+        let mut by_vertex = HashMap::new();
+        for (corner, group) in assigned.iter().enumerate() {
+            if let Some(g) = group {
+                by_vertex.entry(vertex[corner]).or_insert(tangents[*g]);
+            }
+        }
+
+        self.tangents.clear();
+        let mut indices = HashMap::new();
+        for (f, frame) in frames.iter().enumerate() {
+            for i in 0..3 {
+                let corner = 3 * f + i;
+                let tangent = match assigned[corner] {
+                    Some(g) => tangents[g],
+                    None if frame.degenerate => by_vertex
+                        .get(&vertex[corner])
+                        .copied()
+                        .unwrap_or(DEFAULT_TANGENT),
+                    None => DEFAULT_TANGENT,
+                };
+
+                let key = tangent.to_array().map(f32::to_bits);
+                self.faces[f][i].tangent = Some(*indices.entry(key).or_insert_with(|| {
+                    self.tangents.push(tangent);
+                    self.tangents.len() - 1
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// returns the tangent frame of the nth face
+    fn frame(&self, index: usize) -> Frame {
+        let face = &self.faces[index];
+        let p = face.corners.map(|c| self.vertices[c.position]);
+        let uv = face.corners.map(|c| self.uvs[c.uv]);
+
+        let (d1, d2) = (p[1] - p[0], p[2] - p[0]);
+        let (t21, t31) = (uv[1] - uv[0], uv[2] - uv[0]);
+        let area = t21.x * t31.y - t21.y * t31.x;
+
+        let t = -t31.x * d1 + t21.x * d2;
+        let mut frame = Frame {
+            s: t31.y * d1 - t21.y * d2,
+            preserves_orientation: area > 0.0,
+            group_with_any: true,
+            degenerate: false,
+        };
+
+        if not_zero(area) {
+            let sign = if frame.preserves_orientation {
+                1.0
+            } else {
+                -1.0
+            };
+            let (s, t) = (frame.s.length(), t.length());
+            if not_zero(s) {
+                frame.s *= sign / s;
+            }
+
+            frame.group_with_any = !(not_zero(s / area.abs()) && not_zero(t / area.abs()));
+        }
+
+        frame
+    }
+
+    /// returns the tangent shared by the corners of `group`  
+    /// faces without a uv direction only take the tangent, they don't add to it
+    fn group_tangent(&self, group: &Group, frames: &[Frame]) -> Vec4 {
+        let mut sum = Vec3::ZERO;
+        for &(f, i) in &group.corners {
+            if frames[f].group_with_any {
+                continue;
+            }
+
+            let face = &self.faces[f];
+            let n = self.normals[face[i].normal];
+            // onto the plane the normal faces
+            let project = |v: Vec3| {
+                let v = v - n * n.dot(v);
+                if v.to_array().into_iter().any(not_zero) {
+                    v.normalize()
+                } else {
+                    v
+                }
+            };
+
+            let p = face.corners.map(|c| self.vertices[c.position]);
+            let before = project(p[(i + 2) % 3] - p[i]);
+            let after = project(p[(i + 1) % 3] - p[i]);
+            let angle = before.dot(after).clamp(-1.0, 1.0).acos();
+            sum += project(frames[f].s) * angle;
+        }
+
+        if sum.to_array().into_iter().any(not_zero) {
+            sum = sum.normalize();
+        }
+
+        let sign = if group.preserves_orientation {
+            1.0
+        } else {
+            -1.0
+        };
+        sum.extend(sign)
+    }
+}
+
+/// the tangent of corners in no group
+const DEFAULT_TANGENT: Vec4 = Vec4::new(1.0, 0.0, 0.0, -1.0);
+
+/// the reference treats anything smaller as zero
+fn not_zero(x: f32) -> bool {
+    x.abs() > f32::MIN_POSITIVE
+}
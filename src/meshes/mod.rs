@@ -1,7 +1,17 @@
+mod halfedge;
 mod index;
 mod polygon;
+mod separate;
 mod triangle;
+mod validate;
 
-pub use index::IndexMesh;
-pub use polygon::PolyMesh;
-pub use triangle::TriMesh;
+pub use halfedge::{FaceId, HalfEdgeError, HalfEdgeId, HalfEdgeMesh, VertexId};
+pub use index::{
+    Attribute, Corner, Face, FillMethod, FillOptions, IndexError, IndexMesh, Lod, LodChain,
+    LoopOptions, NormalOptions, NormalWeighting, OrientMethod, RepairChange, RepairOptions,
+    SimplifyOptions, VertexBuffer,
+};
+pub use polygon::{CatmullClarkOptions, PolyMesh};
+pub use separate::{Connectivity, SeparateOptions};
+pub use triangle::{Contour, Layer, LayerSpacing, Orientation, TriMesh};
+pub use validate::MeshReport;
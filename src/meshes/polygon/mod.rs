@@ -1,54 +1,135 @@
-use std::ops::{Index, IndexMut};
+mod subdivide;
+
+use std::{
+    collections::BTreeMap,
+    ops::{Index, IndexMut},
+};
 
 use glam::Vec3;
 
-use crate::polygon::Polygon;
+use crate::{
+    attribute::{Attributes, Source},
+    polygon::Polygon,
+};
+
+use super::TriMesh;
+
+pub use subdivide::CatmullClarkOptions;
 
 /// A Mesh made up of triangles
 #[derive(Clone, Default, Debug)]
 pub struct PolyMesh {
     pub polygons: Vec<Polygon>,
+    /// custom values for every polygon vertex in order  
+    /// keep them in step when changing `polygons` directly
+    pub attributes: Attributes,
 }
 
 impl PolyMesh {
     pub fn new(polygons: Vec<Polygon>) -> Self {
-        Self { polygons }
+        Self {
+            polygons,
+            attributes: Attributes::default(),
+        }
     }
 
     /// adds a triangle to the mesh
+    /// with default attribute values
     pub fn add(&mut self, polygon: Polygon) {
         self.polygons.push(polygon);
+        if !self.attributes.is_empty() {
+            self.attributes.resize(self.vertex_count());
+        }
     }
 
     /// removes a triangle at index from the mesh
+    /// along with its attribute values
     pub fn remove(&mut self, index: usize) -> Polygon {
+        if !self.attributes.is_empty() {
+            let first = self.polygons[..index]
+                .iter()
+                .map(Polygon::len)
+                .sum::<usize>();
+            self.attributes
+                .remove_range(first..first + self.polygons[index].len());
+        }
+
         self.polygons.remove(index)
     }
 
+    /// returns the amount of vertices in every polygon together
+    pub fn vertex_count(&self) -> usize {
+        self.polygons.iter().map(Polygon::len).sum()
+    }
+
     /// returns the amount of triangles this mesh has
     pub fn polycount(&self) -> usize {
         self.polygons.len()
     }
 
-    /// returns a point with the maximum x, y and z values
-    pub fn max(&self) -> Vec3 {
-        let mut out = self[0].max();
-        for i in 1..self.polycount() {
-            out = out.max(self[i].max());
+    /// returns a point with the maximum x, y and z values  
+    /// returns None if there are no vertices
+    pub fn max(&self) -> Option<Vec3> {
+        self.points().reduce(Vec3::max)
+    }
+
+    /// returns a point with the minimum x, y, and z values  
+    /// returns None if there are no vertices
+    pub fn min(&self) -> Option<Vec3> {
+        self.points().reduce(Vec3::min)
+    }
+
+    /// Splits this mesh into a mesh for every material
+    ///
+    /// returns the meshes along with their material, ordered by material  
+    /// each mesh keeps the order of its polygons
+    pub fn split_by_material(self) -> Vec<(u32, Self)> {
+        let mut materials: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+        let mut starts = Vec::with_capacity(self.polycount());
+        let mut first = 0;
+        for (i, p) in self.polygons.iter().enumerate() {
+            materials.entry(p.material).or_default().push(i);
+            starts.push(first);
+            first += p.len();
         }
 
-        out
+        materials
+            .into_iter()
+            .map(|(material, part)| {
+                let sources: Vec<Source> = part
+                    .iter()
+                    .flat_map(|&i| {
+                        (starts[i]..starts[i] + self.polygons[i].len()).map(Source::Copy)
+                    })
+                    .collect();
+                let polygons = part.into_iter().map(|i| self.polygons[i].clone());
+                let mut mesh = Self::new(polygons.collect());
+                mesh.attributes = self.attributes.gather(&sources);
+                (material, mesh)
+            })
+            .collect()
     }
 
-    /// returns a point with the minimum x, y, and z values
-    pub fn min(&self) -> Vec3 {
-        let mut out = self[0].min();
-        for i in 1..self.polycount() {
-            out = out.min(self[i].min());
+    /// Combines `meshes` into one mesh, in order
+    ///
+    /// attribute channels missing from some meshes get default values there
+    pub fn merge(meshes: impl IntoIterator<Item = Self>) -> Self {
+        let mut out = Self::default();
+        for mut mesh in meshes {
+            let len = out.vertex_count();
+            out.attributes
+                .append(len, &mesh.attributes, mesh.vertex_count());
+            out.polygons.append(&mut mesh.polygons);
         }
 
         out
     }
+
+    fn points(&self) -> impl Iterator<Item = Vec3> + '_ {
+        self.polygons
+            .iter()
+            .flat_map(|p| p.vertices.iter().map(|v| v.point))
+    }
 }
 
 impl Index<usize> for PolyMesh {
@@ -65,3 +146,11 @@ impl IndexMut<usize> for PolyMesh {
         &mut self.polygons[index]
     }
 }
+
+impl From<TriMesh> for PolyMesh {
+    fn from(value: TriMesh) -> Self {
+        let mut mesh = Self::new(value.triangles.into_iter().map(Polygon::from).collect());
+        mesh.attributes = value.attributes;
+        mesh
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::{HashMap, HashSet};
+
+use glam::{Vec2, Vec3};
+
+use crate::{
+    attribute::Source, disjoint::DisjointSet, polygon::Polygon, vertex::Vertex, weld::Welder,
+};
+
+use super::PolyMesh;
+
+/// Options for `PolyMesh::catmull_clark`
+#[derive(Clone, Debug)]
+pub struct CatmullClarkOptions {
+    /// how many times to subdivide
+    pub levels: usize,
+    /// the sharpness of edges between two points  
+    /// an edge stays sharp for this many levels and then smooths out,
+    /// use `f32::INFINITY` to keep it sharp
+    pub creases: Vec<([Vec3; 2], f32)>,
+}
+
+impl Default for CatmullClarkOptions {
+    fn default() -> Self {
+        Self {
+            levels: 1,
+            creases: Vec::new(),
+        }
+    }
+}
+
+impl PolyMesh {
+    /// Subdivides this mesh into quads
+    /// and moves the vertices towards a smooth surface (Catmull–Clark)
+    ///
+    /// polygons sharing a point are connected  
+    /// open boundaries and edges used by more than 2 polygons stay sharp,
+    /// corners used by a single polygon don't move  
+    /// uvs and attributes are interpolated linearly within each polygon,
+    /// so seams stay where they are,
+    /// and normals are averaged from the new polygons
+    /// across every edge that isn't sharp after the last level
+    pub fn catmull_clark(&self, options: &CatmullClarkOptions) -> PolyMesh {
+        let mut mesh = self.clone();
+        let mut creases: HashMap<[[u32; 3]; 2], f32> = HashMap::new();
+        for &([a, b], sharpness) in &options.creases {
+            creases.insert(edge_key(a, b), sharpness);
+        }
+
+        for _ in 0..options.levels {
+            (mesh, creases) = mesh.catmull_clark_level(&creases);
+        }
+
+        mesh
+    }
+
+    /// Subdivides once  
+    /// returns the new mesh and the sharpness of the new edges
+    fn catmull_clark_level(
+        &self,
+        creases: &HashMap<[[u32; 3]; 2], f32>,
+    ) -> (PolyMesh, HashMap<[[u32; 3]; 2], f32>) {
+        let mut welder = Welder::new(0.0);
+        let polygons: Vec<&Polygon> = self.polygons.iter().filter(|p| p.len() >= 3).collect();
+        let faces: Vec<Vec<usize>> = polygons
+            .iter()
+            .map(|p| p.vertices.iter().map(|v| welder.insert(v.point)).collect())
+            .collect();
+        let points = welder.into_points();
+
+        let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (f, face) in faces.iter().enumerate() {
+            for i in 0..face.len() {
+                edges
+                    .entry(key(face[i], face[(i + 1) % face.len()]))
+                    .or_default()
+                    .push(f);
+            }
+        }
+
+        // open boundaries and non manifold edges are always sharp
+        let sharpness = |&(a, b): &(usize, usize)| match edges[&(a, b)].len() {
+            2 => creases
+                .get(&edge_key(points[a], points[b]))
+                .copied()
+                .unwrap_or(0.0),
+            _ => f32::INFINITY,
+        };
+
+        let centers: Vec<Vec3> = faces
+            .iter()
+            .map(|face| face.iter().map(|&v| points[v]).sum::<Vec3>() / face.len() as f32)
+            .collect();
+
+        let mut order: Vec<(usize, usize)> = edges.keys().copied().collect();
+        order.sort_unstable();
+        let mut edge_points = HashMap::with_capacity(order.len());
+        for &edge in &order {
+            let (a, b) = (points[edge.0], points[edge.1]);
+            let sharp = (a + b) * 0.5;
+            let p = match &edges[&edge][..] {
+                &[f, g] => {
+                    let smooth = (a + b + centers[f] + centers[g]) * 0.25;
+                    smooth.lerp(sharp, sharpness(&edge).clamp(0.0, 1.0))
+                }
+                _ => sharp,
+            };
+
+            edge_points.insert(edge, p);
+        }
+
+        let mut neighbours = vec![Vec::new(); points.len()];
+        let mut vertex_faces = vec![Vec::new(); points.len()];
+        for &(a, b) in &order {
+            neighbours[a].push(b);
+            neighbours[b].push(a);
+        }
+
+        for (f, face) in faces.iter().enumerate() {
+            for &v in face {
+                vertex_faces[v].push(f);
+            }
+        }
+
+        let vertex_points: Vec<Vec3> = (0..points.len())
+            .map(|v| {
+                let p = points[v];
+                let n = neighbours[v].len() as f32;
+                if vertex_faces[v].len() == 1 || n == 0.0 {
+                    return p;
+                }
+
+                let q = vertex_faces[v].iter().map(|&f| centers[f]).sum::<Vec3>()
+                    / vertex_faces[v].len() as f32;
+                let r = neighbours[v]
+                    .iter()
+                    .map(|&u| (p + points[u]) * 0.5)
+                    .sum::<Vec3>()
+                    / n;
+                let smooth = (q + r * 2.0 + p * (n - 3.0)) / n;
+
+                let sharp: Vec<(usize, f32)> = neighbours[v]
+                    .iter()
+                    .map(|&u| (u, sharpness(&key(v, u))))
+                    .filter(|&(_, s)| s > 0.0)
+                    .collect();
+                let weight = sharp.iter().map(|&(_, s)| s).sum::<f32>() / sharp.len().max(1) as f32;
+                match sharp[..] {
+                    [] | [_] => smooth,
+                    [(a, _), (b, _)] => {
+                        let crease = (points[a] + p * 6.0 + points[b]) / 8.0;
+                        smooth.lerp(crease, weight.min(1.0))
+                    }
+                    _ => smooth.lerp(p, weight.min(1.0)),
+                }
+            })
+            .collect();
+
+        // where the attributes of every polygon start
+        let mut starts = Vec::with_capacity(polygons.len());
+        let mut first = 0;
+        for polygon in &self.polygons {
+            if polygon.len() >= 3 {
+                starts.push(first);
+            }
+
+            first += polygon.len();
+        }
+
+        // Every corner becomes a quad from the corner,
+        // to the next edge, the center and the previous edge
+        let mut quads = Vec::new();
+        let mut materials = Vec::new();
+        let mut sources = Vec::new();
+        for (f, face) in faces.iter().enumerate() {
+            let polygon = polygons[f];
+            let len = face.len();
+            let start = starts[f];
+            let center_uv = polygon.vertices.iter().map(|v| v.uv).sum::<Vec2>() / len as f32;
+            for i in 0..len {
+                let (prev, next) = ((i + len - 1) % len, (i + 1) % len);
+                quads.push([
+                    (vertex_points[face[i]], polygon[i].uv),
+                    (
+                        edge_points[&key(face[i], face[next])],
+                        (polygon[i].uv + polygon[next].uv) * 0.5,
+                    ),
+                    (centers[f], center_uv),
+                    (
+                        edge_points[&key(face[prev], face[i])],
+                        (polygon[prev].uv + polygon[i].uv) * 0.5,
+                    ),
+                ]);
+                sources.extend([
+                    Source::Copy(start + i),
+                    Source::Lerp(start + i, start + next, 0.5),
+                    Source::Average((start..start + len).collect()),
+                    Source::Lerp(start + prev, start + i, 0.5),
+                ]);
+                materials.push(polygon.material);
+            }
+        }
+
+        // Sharp edges pass what's left of their sharpness to their halves,
+        // and the normals split along the halves of fully sharp edges
+        let mut next = HashMap::new();
+        let mut sharp = HashSet::new();
+        for edge in order {
+            let s = sharpness(&edge);
+            let middle = edge_points[&edge];
+            let halves = [
+                edge_key(vertex_points[edge.0], middle),
+                edge_key(middle, vertex_points[edge.1]),
+            ];
+            if s >= 1.0 {
+                sharp.extend(halves);
+            }
+
+            if edges[&edge].len() == 2 && s > 1.0 {
+                for half in halves {
+                    next.insert(half, s - 1.0);
+                }
+            }
+        }
+
+        let mut mesh = smooth_normals(&quads, &sharp);
+        for (polygon, material) in mesh.polygons.iter_mut().zip(materials) {
+            polygon.material = material;
+        }
+
+        mesh.attributes = self.attributes.gather(&sources);
+        (mesh, next)
+    }
+}
+
+/// Creates polygons from `quads` with normals averaged
+/// across the edges shared by two quads that aren't in `sharp`
+fn smooth_normals(quads: &[[(Vec3, Vec2); 4]], sharp: &HashSet<[[u32; 3]; 2]>) -> PolyMesh {
+    // the corners at both ends of every edge, in the order of `edge_key`
+    let mut edges: HashMap<[[u32; 3]; 2], Vec<[usize; 2]>> = HashMap::new();
+    for (q, quad) in quads.iter().enumerate() {
+        for i in 0..4 {
+            let j = (i + 1) % 4;
+            let corners = if bits(quad[i].0) <= bits(quad[j].0) {
+                [4 * q + i, 4 * q + j]
+            } else {
+                [4 * q + j, 4 * q + i]
+            };
+            edges
+                .entry(edge_key(quad[i].0, quad[j].0))
+                .or_default()
+                .push(corners);
+        }
+    }
+
+    let mut corners = DisjointSet::new(4 * quads.len());
+    for (edge, ends) in &edges {
+        if let [a, b] = ends[..] {
+            if !sharp.contains(edge) {
+                corners.union(a[0], b[0]);
+                corners.union(a[1], b[1]);
+            }
+        }
+    }
+
+    let mut normals = vec![Vec3::ZERO; 4 * quads.len()];
+    for (q, quad) in quads.iter().enumerate() {
+        let normal = newell(quad.map(|(p, _)| p));
+        for i in 0..4 {
+            normals[corners.find(4 * q + i)] += normal;
+        }
+    }
+
+    let polygons = quads
+        .iter()
+        .enumerate()
+        .map(|(q, quad)| {
+            Polygon::new(
+                (0..4)
+                    .map(|i| {
+                        let normal = normals[corners.find(4 * q + i)].normalize_or_zero();
+                        Vertex::new(quad[i].0, quad[i].1, normal)
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+
+    PolyMesh::new(polygons)
+}
+
+/// returns the area weighted normal of a polygon
+fn newell(points: [Vec3; 4]) -> Vec3 {
+    let mut normal = Vec3::ZERO;
+    for i in 0..4 {
+        let (a, b) = (points[i], points[(i + 1) % 4]);
+        normal += Vec3::new(
+            (a.y - b.y) * (a.z + b.z),
+            (a.z - b.z) * (a.x + b.x),
+            (a.x - b.x) * (a.y + b.y),
+        );
+    }
+
+    normal * 0.5
+}
+
+fn bits(p: Vec3) -> [u32; 3] {
+    // adding zero turns -0.0 into 0.0
+    (p + Vec3::ZERO).to_array().map(f32::to_bits)
+}
+
+/// an order independent key for the edge between two points
+fn edge_key(a: Vec3, b: Vec3) -> [[u32; 3]; 2] {
+    let (a, b) = (bits(a), bits(b));
+    [a.min(b), a.max(b)]
+}
+
+fn key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
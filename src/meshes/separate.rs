@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use glam::Vec3;
+
+use crate::disjoint::DisjointSet;
+
+/// Options for `separate_by_loose_parts_with`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SeparateOptions {
+    /// also connects parts whose vertices are within this distance
+    /// even if they don't share an index
+    pub tolerance: Option<f32>,
+    /// what two triangles need to share to be connected
+    pub connectivity: Connectivity,
+}
+
+/// How triangles are connected to each other
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Connectivity {
+    /// triangles sharing at least one vertex are connected
+    #[default]
+    Vertex,
+    /// triangles sharing at least one edge are connected  
+    /// parts touching at a single (bow-tie) vertex stay separate
+    Edge,
+}
+
+/// Merges every two of `points` within `tolerance` of each other,
+/// so points in a chain of close points end up in the same set
+///
+/// points are hashed into cells of `tolerance` size
+/// and compared with the points in the 27 cells around them
+pub(crate) fn near_points(points: &[Vec3], tolerance: f32) -> DisjointSet {
+    let mut set = DisjointSet::new(points.len());
+    let tolerance = tolerance.max(0.0);
+    let key = |p: Vec3| {
+        if tolerance == 0.0 {
+            // `+ 0.0` turns -0.0 into 0.0 so both hash the same
+            (p + Vec3::ZERO).to_array().map(|x| x.to_bits() as i64)
+        } else {
+            (p / tolerance).floor().to_array().map(|x| x as i64)
+        }
+    };
+
+    let reach = if tolerance == 0.0 { 0 } else { 1 };
+    let mut cells: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+    for (i, &p) in points.iter().enumerate() {
+        let k = key(p);
+        for x in -reach..=reach {
+            for y in -reach..=reach {
+                for z in -reach..=reach {
+                    let Some(cell) = cells.get(&[k[0] + x, k[1] + y, k[2] + z]) else {
+                        continue;
+                    };
+
+                    for &j in cell {
+                        if points[j].distance_squared(p) <= tolerance * tolerance {
+                            set.union(i, j);
+                        }
+                    }
+                }
+            }
+        }
+
+        cells.entry(k).or_default().push(i);
+    }
+
+    set
+}
+
+/// Groups triangles into connected parts
+///
+/// `triangles` holds the vertex ids of each triangle,
+/// vertices already merged in `vertices` count as the same vertex  
+/// parts are ordered by their first triangle,
+/// and each part lists it's triangles in ascending order
+pub(crate) fn loose_parts(
+    triangles: &[[usize; 3]],
+    mut vertices: DisjointSet,
+    connectivity: Connectivity,
+) -> Vec<Vec<usize>> {
+    let roots: Vec<usize> = match connectivity {
+        Connectivity::Vertex => {
+            for t in triangles {
+                vertices.union(t[0], t[1]);
+                vertices.union(t[1], t[2]);
+            }
+
+            triangles.iter().map(|t| vertices.find(t[0])).collect()
+        }
+        Connectivity::Edge => {
+            let mut set = DisjointSet::new(triangles.len());
+            let mut edges = HashMap::new();
+            for (i, t) in triangles.iter().enumerate() {
+                let t = t.map(|v| vertices.find(v));
+                for [a, b] in [[t[0], t[1]], [t[1], t[2]], [t[2], t[0]]] {
+                    if a == b {
+                        continue;
+                    }
+
+                    let first = *edges.entry([a.min(b), a.max(b)]).or_insert(i);
+                    set.union(first, i);
+                }
+            }
+
+            (0..triangles.len()).map(|i| set.find(i)).collect()
+        }
+    };
+
+    let mut parts: Vec<Vec<usize>> = Vec::new();
+    let mut part_of = HashMap::new();
+    for (i, root) in roots.into_iter().enumerate() {
+        let part = *part_of.entry(root).or_insert_with(|| {
+            parts.push(Vec::new());
+            parts.len() - 1
+        });
+
+        parts[part].push(i);
+    }
+
+    parts
+}
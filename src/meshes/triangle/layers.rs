@@ -0,0 +1,174 @@
+use glam::Vec3;
+
+use crate::plane::Plane;
+
+use super::{section::Section, Contour, TriMesh};
+
+/// How `TriMesh::layers` spaces it's layers
+#[derive(Clone, Copy, Debug)]
+pub enum LayerSpacing {
+    /// layers of a fixed thickness
+    Thickness(f32),
+    /// this many evenly spaced layers
+    Count(usize),
+    /// layers between `min` and `max` thick,
+    /// as thick as possible while keeping the stair step (cusp) height
+    /// of sloped surfaces below `tolerance`
+    Adaptive { min: f32, max: f32, tolerance: f32 },
+}
+
+impl LayerSpacing {
+    /// returns whether the thicknesses are positive and finite,
+    /// `min` isn't above `max` and the tolerance isn't negative
+    fn is_valid(&self) -> bool {
+        let positive = |x: f32| x > 0.0 && x.is_finite();
+        match *self {
+            Self::Thickness(thickness) => positive(thickness),
+            Self::Count(_) => true,
+            Self::Adaptive {
+                min,
+                max,
+                tolerance,
+            } => positive(min) && positive(max) && min <= max && tolerance >= 0.0,
+        }
+    }
+}
+
+/// A single layer of a `TriMesh` cut along an axis
+#[derive(Clone, Debug, Default)]
+pub struct Layer {
+    /// where the layer is cut along the axis,
+    /// halfway through the layer
+    pub height: f32,
+    pub thickness: f32,
+    pub contours: Vec<Contour>,
+}
+
+impl Layer {
+    /// returns the area enclosed by this layer's closed contours
+    pub fn area(&self) -> f32 {
+        self.contours
+            .iter()
+            .filter(|c| c.closed)
+            .map(|c| c.area())
+            .sum()
+    }
+
+    /// returns the volume of this layer  
+    /// it's area extruded by it's thickness
+    pub fn volume(&self) -> f32 {
+        self.area() * self.thickness
+    }
+
+    /// returns two points with the minimum and maximum x, y, and z values
+    /// of this layer's contours
+    pub fn bounds(&self) -> Option<[Vec3; 2]> {
+        let mut points = self.contours.iter().flat_map(|c| &c.points);
+        let first = *points.next()?;
+        Some(points.fold([first, first], |b, p| [b[0].min(*p), b[1].max(*p)]))
+    }
+}
+
+impl TriMesh {
+    /// Slices this mesh into layers along `axis`
+    ///
+    /// triangles are swept in order along the axis
+    /// so each layer only cuts the triangles it passes through
+    ///
+    /// returns no layers if `spacing` has a thickness that isn't positive and finite,
+    /// a `min` above `max` or a negative tolerance  
+    /// stops early when layers get too thin to move past the float precision
+    pub fn layers(&self, axis: Vec3, spacing: LayerSpacing) -> Vec<Layer> {
+        if !spacing.is_valid() {
+            return Vec::new();
+        }
+
+        let axis = axis.normalize();
+        let ranges: Vec<[f32; 2]> = self
+            .triangles
+            .iter()
+            .map(|t| {
+                let d = t.points.map(|p| p.dot(axis));
+                [d[0].min(d[1]).min(d[2]), d[0].max(d[1]).max(d[2])]
+            })
+            .collect();
+
+        let mut order: Vec<usize> = (0..ranges.len()).collect();
+        order.sort_by(|a, b| ranges[*a][0].total_cmp(&ranges[*b][0]));
+
+        let (bottom, top) = match ranges
+            .iter()
+            .map(|r| (r[0], r[1]))
+            .reduce(|a, b| (a.0.min(b.0), a.1.max(b.1)))
+        {
+            Some(range) => range,
+            None => return Vec::new(),
+        };
+
+        let section = Section::new(self);
+        let mut active: Vec<usize> = Vec::new();
+        let mut next = 0;
+        let mut layers = Vec::new();
+
+        // ignore layers starting in the last bit of the mesh from rounding errors
+        let end = top - (top - bottom) * 1e-5;
+        let mut start = bottom;
+        while start < end {
+            let thickness = match spacing {
+                LayerSpacing::Thickness(thickness) => thickness,
+                LayerSpacing::Count(count) => (top - bottom) / count.max(1) as f32,
+                LayerSpacing::Adaptive {
+                    min,
+                    max,
+                    tolerance,
+                } => {
+                    // the cusp height of a face is thickness * |normal.dot(axis)|
+                    let slope = order
+                        .iter()
+                        .skip(next)
+                        .take_while(|&&i| ranges[i][0] < start + max)
+                        .chain(&active)
+                        .filter(|&&i| ranges[i][1] >= start)
+                        .map(|&i| {
+                            let t = &self.triangles[i];
+                            let normal = (t[1] - t[0]).cross(t[2] - t[0]).normalize_or_zero();
+                            normal.dot(axis).abs()
+                        })
+                        .fold(0.0, f32::max);
+
+                    if slope > 0.0 {
+                        (tolerance / slope).clamp(min, max)
+                    } else {
+                        max
+                    }
+                }
+            };
+
+            if thickness <= 0.0 || !thickness.is_finite() || start + thickness == start {
+                break;
+            }
+
+            let height = start + thickness * 0.5;
+            while next < order.len() && ranges[order[next]][0] <= height {
+                active.push(order[next]);
+                next += 1;
+            }
+
+            active.retain(|&i| ranges[i][1] >= height);
+
+            let plane = Plane::new(axis * height, axis);
+            layers.push(Layer {
+                height,
+                thickness,
+                contours: section.cut(&plane, active.iter().copied()),
+            });
+
+            start = match spacing {
+                LayerSpacing::Adaptive { .. } => start + thickness,
+                _ => bottom + thickness * layers.len() as f32,
+            };
+        }
+
+        layers
+    }
+}
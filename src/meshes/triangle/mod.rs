@@ -1,31 +1,56 @@
+mod layers;
 mod modifiers;
+mod section;
 
 use std::ops::{Index, IndexMut};
 
 use glam::Vec3;
 
-use crate::triangle::Triangle;
+use crate::{
+    attribute::{Attributes, Source},
+    plane::Plane,
+    triangle::Triangle,
+};
 
-use super::IndexMesh;
+use super::{IndexError, IndexMesh, PolyMesh};
+use section::Section;
+
+pub use layers::{Layer, LayerSpacing};
+pub use section::{Contour, Orientation};
 
 /// A Mesh made up of triangles
 #[derive(Clone, Default, Debug)]
 pub struct TriMesh {
     pub triangles: Vec<Triangle>,
+    /// custom values for every corner, 3 per triangle in order  
+    /// keep them in step when changing `triangles` directly
+    pub attributes: Attributes,
 }
 
 impl TriMesh {
     pub fn new(triangles: Vec<Triangle>) -> Self {
-        Self { triangles }
+        Self {
+            triangles,
+            attributes: Attributes::default(),
+        }
     }
 
     /// adds a triangle to the mesh
+    /// with default attribute values
     pub fn add(&mut self, triangle: Triangle) {
         self.triangles.push(triangle);
+        if !self.attributes.is_empty() {
+            self.attributes.resize(self.triangles.len() * 3);
+        }
     }
 
     /// removes a triangle at index from the mesh
+    /// along with its attribute values
     pub fn remove(&mut self, index: usize) -> Triangle {
+        if !self.attributes.is_empty() {
+            self.attributes.remove_range(index * 3..index * 3 + 3);
+        }
+
         self.triangles.remove(index)
     }
 
@@ -34,29 +59,44 @@ impl TriMesh {
         self.triangles.len()
     }
 
-    /// returns two points with the minimum and maximum x, y, and z values
-    pub fn min_max(&self) -> [Vec3; 2] {
-        let mut out = [self[0].min(), self[0].max()];
-        for i in 1..self.tricount() {
-            out[0] = out[0].min(self[i].min());
-            out[1] = out[1].max(self[i].max());
+    /// returns two points with the minimum and maximum x, y, and z values  
+    /// returns None if this mesh has no triangles
+    pub fn min_max(&self) -> Option<[Vec3; 2]> {
+        let first = self.triangles.first()?;
+        let mut out = [first.min(), first.max()];
+        for t in &self.triangles[1..] {
+            out[0] = out[0].min(t.min());
+            out[1] = out[1].max(t.max());
         }
 
-        out
+        Some(out)
     }
 
-    /// Calculates the surface area of this mesh
-    pub fn surface_area(&self) -> f32 {
+    /// returns the contours where `plane` cuts through this mesh
+    ///
+    /// contours are chained through shared edges,
+    /// outlines run counter clockwise and holes clockwise
+    /// when looking down at the plane
+    pub fn section(&self, plane: &Plane) -> Vec<Contour> {
+        Section::new(self).cut(plane, 0..self.tricount())
+    }
+
+    /// Calculates the volume enclosed by this mesh
+    ///
+    /// only meaningful for closed meshes,
+    /// negative when the triangles face inwards
+    pub fn volume(&self) -> f32 {
         let mut sum = 0.0;
         for t in &self.triangles {
-            let corner = t[0];
-            let a = t[1] - corner;
-            let b = t[2] - corner;
-
-            sum += a.cross(b).length();
+            sum += t[0].dot(t[1].cross(t[2]));
         }
 
-        sum
+        sum / 6.0
+    }
+
+    /// Calculates the surface area of this mesh
+    pub fn surface_area(&self) -> f32 {
+        self.triangles.iter().map(Triangle::area).sum()
     }
 }
 
@@ -75,28 +115,33 @@ impl IndexMut<usize> for TriMesh {
     }
 }
 
-impl From<IndexMesh> for TriMesh {
-    fn from(value: IndexMesh) -> Self {
+impl TryFrom<IndexMesh> for TriMesh {
+    type Error = IndexError;
+
+    /// returns an error if a corner is out of range
+    fn try_from(value: IndexMesh) -> Result<Self, Self::Error> {
+        let mut mesh: Self = value.triangles().collect::<Result<_, _>>().map(Self::new)?;
+        mesh.attributes = value.corner_attributes();
+        Ok(mesh)
+    }
+}
+
+impl From<PolyMesh> for TriMesh {
+    /// Splits every polygon into a fan of triangles
+    fn from(value: PolyMesh) -> Self {
         let mut out = Self::default();
-        for i in 0..value.indices.len() / 3 {
-            let i = i * 3;
-            let index = [value.indices[i], value.indices[i + 1], value.indices[i + 2]];
-
-            out.add(Triangle::new(
-                [
-                    value.vertices[index[0][0]],
-                    value.vertices[index[1][0]],
-                    value.vertices[index[2][0]],
-                ],
-                [
-                    value.uvs[index[0][1]],
-                    value.uvs[index[1][1]],
-                    value.uvs[index[2][1]],
-                ],
-                value.normals[index[0][2]],
-            ));
+        let mut sources = Vec::new();
+        let mut first = 0;
+        for polygon in value.polygons {
+            for i in 1..polygon.len().saturating_sub(1) {
+                sources.extend([first, first + i, first + i + 1].map(Source::Copy));
+            }
+
+            first += polygon.len();
+            out.triangles.append(&mut polygon.into());
         }
 
+        out.attributes = value.attributes.gather(&sources);
         out
     }
 }
@@ -1,64 +1,252 @@
-use crate::triangle::Triangle;
+use std::collections::BTreeMap;
 
-use super::TriMesh;
+use crate::{
+    attribute::Source,
+    disjoint::DisjointSet,
+    meshes::{
+        separate::{loose_parts, near_points},
+        FillOptions, IndexMesh, LoopOptions, OrientMethod, RepairChange, RepairOptions,
+        SeparateOptions, SimplifyOptions,
+    },
+    plane::Plane,
+    triangle::Triangle,
+    weld::Welder,
+};
+
+use super::{
+    section::{caps, Section},
+    TriMesh,
+};
 
 impl TriMesh {
     /// Subdivides every triangle into 4 other triangles
     pub fn subdivide(&self) -> Self {
         let mut output = Self::new(Vec::new());
-        for t in &self.triangles {
+        let mut sources = Vec::with_capacity(self.tricount() * 12);
+        for (i, t) in self.triangles.iter().enumerate() {
             let tris = t.subdivide();
             output.add(tris[0]);
             output.add(tris[1]);
             output.add(tris[2]);
             output.add(tris[3]);
+
+            // the same corners as `Triangle::subdivide`
+            let [a, b, c] = [0, 1, 2].map(|c| Source::Copy(3 * i + c));
+            let [ab, bc, ca] = [0, 1, 2].map(|c| Source::Lerp(3 * i + c, 3 * i + (c + 1) % 3, 0.5));
+            sources.extend([
+                a,
+                ab.clone(),
+                ca.clone(),
+                ab.clone(),
+                b,
+                bc.clone(),
+                ca.clone(),
+                bc.clone(),
+                c,
+                ab,
+                bc,
+                ca,
+            ]);
         }
 
+        output.attributes = self.attributes.gather(&sources);
         output
     }
 
-    /// Seperates any loose parts into there own mesh
-    pub fn separate_by_loose_parts(self) -> Vec<Self> {
-        let mut meshes: Vec<Self> = Vec::new();
-        for t in self.triangles {
-            let mut out = vec![t];
-
-            let mut i = 0;
-            while i < meshes.len() {
-                if !is_connected(&out, &meshes[i].triangles) {
-                    i += 1;
-                    continue;
+    /// Simplifies this mesh by collapsing edges
+    /// in order of their quadric error
+    ///
+    /// see `IndexMesh::simplify`,
+    /// the simplified triangles get their normals from their winding
+    pub fn simplify(&self, options: SimplifyOptions) -> Self {
+        let mesh = IndexMesh::from_trimesh(self, 0.0).simplify(options);
+        Self::from_winding(&mesh)
+    }
+
+    /// Subdivides every triangle into 4 other triangles
+    /// and moves the vertices towards a smooth surface
+    ///
+    /// see `IndexMesh::loop_subdivide`,
+    /// `options.creases` index the vertices of `IndexMesh::from_trimesh`  
+    /// the new triangles get their normals from their winding
+    pub fn loop_subdivide(&self, options: &LoopOptions) -> Self {
+        let mesh = IndexMesh::from_trimesh(self, 0.0).loop_subdivide(options);
+        Self::from_winding(&mesh)
+    }
+
+    /// Fills every hole within the limits of `options` with new triangles  
+    /// returns the amount of filled holes
+    ///
+    /// see `IndexMesh::fill_holes`,
+    /// triangles are connected where their points are equal
+    pub fn fill_holes(&mut self, options: FillOptions) -> usize {
+        let mut mesh = IndexMesh::from_trimesh(self, 0.0);
+        let filled = mesh.fill_holes(options);
+        *self = Self::try_from(mesh).expect("filling holes only adds faces with valid indices");
+        filled
+    }
+
+    /// Makes triangles sharing an edge wind the same way,
+    /// then flips every closed connected part facing inwards  
+    /// returns the flipped triangles
+    ///
+    /// see `IndexMesh::orient`,
+    /// triangles are connected where their points are equal
+    pub fn orient(&mut self, method: OrientMethod) -> Vec<usize> {
+        let flipped = IndexMesh::from_trimesh(self, 0.0).flips(method);
+        for &t in &flipped {
+            self.triangles[t] = self.triangles[t].flip();
+            if !self.attributes.is_empty() {
+                self.attributes.swap(3 * t, 3 * t + 2);
+            }
+        }
+
+        flipped
+    }
+
+    /// Fixes the problems `analyze` finds, as far as `options` allow
+    ///
+    /// see `IndexMesh::repair`,
+    /// vertices in the changes index the vertices of `IndexMesh::from_trimesh`  
+    /// split vertices are only kept apart by an `IndexMesh`,
+    /// here they share a point again
+    pub fn repair(&self, options: RepairOptions) -> (Self, Vec<RepairChange>) {
+        let (mesh, log) = IndexMesh::from_trimesh(self, 0.0).repair(options);
+        let mesh = Self::try_from(mesh).expect("repairing only adds faces with valid indices");
+        (mesh, log)
+    }
+
+    /// Creates a mesh from the faces of `mesh`
+    /// with normals from their winding
+    fn from_winding(mesh: &IndexMesh) -> Self {
+        let triangles = mesh
+            .faces
+            .iter()
+            .map(|face| {
+                let points = face.positions().map(|p| mesh.vertices[p]);
+                let uvs = face.corners.map(|c| mesh.uvs[c.uv]);
+                let t = Triangle::from_points_uvs(points, uvs);
+                Triangle {
+                    normal: t.normal.normalize_or_zero(),
+                    material: face.material,
+                    ..t
                 }
+            })
+            .collect();
 
-                out.append(&mut meshes.remove(i).triangles);
+        let mut out = Self::new(triangles);
+        out.attributes = mesh.corner_attributes();
+        out
+    }
+
+    /// Slices this mesh in half along `plane`
+    /// returning the parts above and below it
+    ///
+    /// closed loops along the cut are capped with new faces,
+    /// loops running clockwise become holes in the caps  
+    /// the caps use uvs projected onto the plane,
+    /// material 0 and default attribute values
+    pub fn slice(&self, plane: &Plane) -> (Self, Self) {
+        let mut above = Self::default();
+        let mut below = Self::default();
+        let mut sources = [Vec::new(), Vec::new()];
+        for (i, t) in self.triangles.iter().enumerate() {
+            let (a, b) = t.slice_sources(plane);
+            for (side, pieces) in [(0, a), (1, b)] {
+                let mesh = if side == 0 { &mut above } else { &mut below };
+                for (t, corners) in pieces {
+                    mesh.triangles.push(t);
+                    sources[side].extend(corners.iter().map(|c| c.offset(3 * i)));
+                }
             }
+        }
 
-            meshes.push(TriMesh::new(out));
+        above.attributes = self.attributes.gather(&sources[0]);
+        below.attributes = self.attributes.gather(&sources[1]);
+
+        let contours = Section::new(self).cut(plane, 0..self.tricount());
+        for cap in caps(plane, &contours) {
+            above.add(cap.flip());
+            below.add(cap);
         }
 
-        meshes
+        (above, below)
     }
-}
 
-fn is_connected(a: &[Triangle], b: &[Triangle]) -> bool {
-    for a in a {
-        for b in b {
-            if !(a[0] == b[0]
-                || a[0] == b[1]
-                || a[0] == b[2]
-                || a[1] == b[0]
-                || a[1] == b[1]
-                || a[1] == b[2]
-                || a[2] == b[0]
-                || a[2] == b[1]
-                || a[2] == b[2])
-            {
-                continue;
-            }
+    /// Separates any loose parts into their own mesh
+    ///
+    /// triangles are connected when they share a vertex position
+    pub fn separate_by_loose_parts(self) -> Vec<Self> {
+        self.separate_by_loose_parts_with(SeparateOptions::default())
+    }
+
+    /// Separates any loose parts into their own mesh
+    ///
+    /// vertex positions are hashed into a vertex table,
+    /// and points within `options.tolerance` of each other connect  
+    /// parts are ordered by their first triangle
+    pub fn separate_by_loose_parts_with(self, options: SeparateOptions) -> Vec<Self> {
+        let mut welder = Welder::new(0.0);
+        let ids: Vec<[usize; 3]> = self
+            .triangles
+            .iter()
+            .map(|t| t.points.map(|p| welder.insert(p)))
+            .collect();
+
+        let vertices = match options.tolerance {
+            Some(tolerance) => near_points(welder.points(), tolerance),
+            None => DisjointSet::new(welder.len()),
+        };
+        loose_parts(&ids, vertices, options.connectivity)
+            .into_iter()
+            .map(|part| {
+                let sources: Vec<Source> = part
+                    .iter()
+                    .flat_map(|&i| [0, 1, 2].map(|c| Source::Copy(3 * i + c)))
+                    .collect();
+                let mut mesh = Self::new(part.into_iter().map(|i| self.triangles[i]).collect());
+                mesh.attributes = self.attributes.gather(&sources);
+                mesh
+            })
+            .collect()
+    }
 
-            return true;
+    /// Splits this mesh into a mesh for every material
+    ///
+    /// returns the meshes along with their material, ordered by material  
+    /// each mesh keeps the order of its triangles
+    pub fn split_by_material(self) -> Vec<(u32, Self)> {
+        let mut materials: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+        for (i, t) in self.triangles.iter().enumerate() {
+            materials.entry(t.material).or_default().push(i);
         }
+
+        materials
+            .into_iter()
+            .map(|(material, part)| {
+                let sources: Vec<Source> = part
+                    .iter()
+                    .flat_map(|&i| [0, 1, 2].map(|c| Source::Copy(3 * i + c)))
+                    .collect();
+                let mut mesh = Self::new(part.into_iter().map(|i| self.triangles[i]).collect());
+                mesh.attributes = self.attributes.gather(&sources);
+                (material, mesh)
+            })
+            .collect()
     }
 
-    false
+    /// Combines `meshes` into one mesh, in order
+    ///
+    /// attribute channels missing from some meshes get default values there
+    pub fn merge(meshes: impl IntoIterator<Item = Self>) -> Self {
+        let mut out = Self::default();
+        for mut mesh in meshes {
+            let len = out.tricount() * 3;
+            out.attributes
+                .append(len, &mesh.attributes, mesh.tricount() * 3);
+            out.triangles.append(&mut mesh.triangles);
+        }
+
+        out
+    }
 }
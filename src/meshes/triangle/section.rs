@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use glam::{Vec2, Vec3};
+
+use crate::{
+    plane::{Plane, Side},
+    triangle::Triangle,
+    triangulate::{contains, signed_area, triangulate},
+    weld::Welder,
+};
+
+use super::TriMesh;
+
+/// Where a plane cuts through a mesh,
+/// either at a vertex or along an edge between two vertices
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum CutPoint {
+    Vertex(usize),
+    Edge(usize, usize),
+}
+
+/// A polyline where a plane cuts through a mesh
+#[derive(Clone, Debug, Default)]
+pub struct Contour {
+    pub points: Vec<Vec3>,
+    /// the points along the plane's basis, see `Plane::project`
+    pub points_2d: Vec<Vec2>,
+    /// whether the last point connects back to the first  
+    /// contours are open when the mesh has holes along the cut
+    pub closed: bool,
+    /// whether a closed contour is an outline or a hole,
+    /// None for open contours
+    pub orientation: Option<Orientation>,
+}
+
+/// Which way a closed `Contour` runs around the mesh
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    /// counter clockwise when looking down at the plane,
+    /// the mesh is inside the contour
+    Outer,
+    /// clockwise when looking down at the plane,
+    /// the mesh is outside the contour
+    Hole,
+}
+
+impl Contour {
+    /// returns the area enclosed by this contour  
+    /// positive for outlines and negative for holes
+    pub fn area(&self) -> f32 {
+        signed_area(&self.points_2d)
+    }
+
+    /// returns the length of this contour
+    pub fn length(&self) -> f32 {
+        let mut sum = 0.0;
+        for i in 1..self.points.len() {
+            sum += self.points[i].distance(self.points[i - 1]);
+        }
+
+        if self.closed && self.points.len() > 1 {
+            sum += self.points[0].distance(self.points[self.points.len() - 1]);
+        }
+
+        sum
+    }
+}
+
+/// The vertex table of a mesh, used to cut it with planes
+///
+/// cut segments are chained through the mesh's shared edges
+/// so chaining never relies on comparing intersection points
+pub(crate) struct Section {
+    points: Vec<Vec3>,
+    ids: Vec<[usize; 3]>,
+}
+
+impl Section {
+    pub fn new(mesh: &TriMesh) -> Self {
+        let mut welder = Welder::new(0.0);
+        let ids = mesh
+            .triangles
+            .iter()
+            .map(|t| t.points.map(|p| welder.insert(p)))
+            .collect();
+
+        Self {
+            points: welder.into_points(),
+            ids,
+        }
+    }
+
+    /// Cuts `triangles` with `plane`
+    ///
+    /// contours run counter clockwise around the mesh
+    /// when looking down at the plane
+    pub fn cut(&self, plane: &Plane, triangles: impl IntoIterator<Item = usize>) -> Vec<Contour> {
+        let mut segments = Vec::new();
+        for t in triangles {
+            let ids = self.ids[t];
+            let points = ids.map(|i| self.points[i]);
+            let sides = points.map(|p| plane.side(p));
+
+            let mut cuts = Vec::with_capacity(3);
+            for i in 0..3 {
+                if sides[i] == Side::Coplanar {
+                    cuts.push(CutPoint::Vertex(ids[i]));
+                }
+            }
+
+            for i in 0..3 {
+                let j = (i + 1) % 3;
+                if matches!(
+                    (sides[i], sides[j]),
+                    (Side::Above, Side::Below) | (Side::Below, Side::Above)
+                ) {
+                    cuts.push(CutPoint::Edge(ids[i].min(ids[j]), ids[i].max(ids[j])));
+                }
+            }
+
+            if cuts.len() != 2 || cuts[0] == cuts[1] {
+                continue;
+            }
+
+            // An edge lying on the plane is shared by two triangles,
+            // only the one below the plane adds it
+            if sides.iter().filter(|s| **s == Side::Coplanar).count() == 2
+                && !sides.contains(&Side::Below)
+            {
+                continue;
+            }
+
+            let normal = (points[1] - points[0]).cross(points[2] - points[0]);
+            let direction = plane.normal.cross(normal);
+            let (a, b) = (cuts[0], cuts[1]);
+            if (self.position(plane, b) - self.position(plane, a)).dot(direction) < 0.0 {
+                segments.push((b, a));
+            } else {
+                segments.push((a, b));
+            }
+        }
+
+        self.chain(plane, &segments)
+    }
+
+    /// Links segments sharing a cut point into contours  
+    /// open contours start where no segment ends
+    fn chain(&self, plane: &Plane, segments: &[(CutPoint, CutPoint)]) -> Vec<Contour> {
+        let mut outgoing: HashMap<CutPoint, Vec<usize>> = HashMap::new();
+        let mut incoming: HashMap<CutPoint, usize> = HashMap::new();
+        for (i, s) in segments.iter().enumerate() {
+            outgoing.entry(s.0).or_default().push(i);
+            *incoming.entry(s.1).or_default() += 1;
+        }
+
+        let mut used = vec![false; segments.len()];
+        let walk = |start: usize, used: &mut [bool]| {
+            let mut keys = vec![segments[start].0];
+            let mut current = start;
+            let mut closed = false;
+            loop {
+                used[current] = true;
+                let end = segments[current].1;
+                if end == keys[0] {
+                    closed = true;
+                    break;
+                }
+
+                keys.push(end);
+                match outgoing
+                    .get(&end)
+                    .and_then(|next| next.iter().find(|&&n| !used[n]))
+                {
+                    Some(&next) => current = next,
+                    None => break,
+                }
+            }
+
+            let points: Vec<Vec3> = keys.into_iter().map(|k| self.position(plane, k)).collect();
+            let points_2d: Vec<Vec2> = points.iter().map(|p| plane.project(*p)).collect();
+            let orientation = match (closed, signed_area(&points_2d) >= 0.0) {
+                (false, _) => None,
+                (true, true) => Some(Orientation::Outer),
+                (true, false) => Some(Orientation::Hole),
+            };
+
+            Contour {
+                points,
+                points_2d,
+                closed,
+                orientation,
+            }
+        };
+
+        let mut chains = Vec::new();
+        for i in 0..segments.len() {
+            if !used[i] && !incoming.contains_key(&segments[i].0) {
+                chains.push(walk(i, &mut used));
+            }
+        }
+
+        for i in 0..segments.len() {
+            if !used[i] {
+                chains.push(walk(i, &mut used));
+            }
+        }
+
+        chains
+    }
+
+    fn position(&self, plane: &Plane, cut: CutPoint) -> Vec3 {
+        match cut {
+            CutPoint::Vertex(i) => self.points[i],
+            CutPoint::Edge(a, b) => plane.cross_point(self.points[a], self.points[b]),
+        }
+    }
+}
+
+/// Triangulates the closed `contours` into faces covering the cut  
+/// the faces point along the plane's normal and use planar uvs
+pub(crate) fn caps(plane: &Plane, contours: &[Contour]) -> Vec<Triangle> {
+    let normal = plane.normal.normalize();
+    let loops: Vec<(&Contour, f32)> = contours
+        .iter()
+        .filter(|c| c.closed && c.points.len() >= 3)
+        .map(|c| (c, c.area()))
+        .collect();
+
+    // Give every hole to the smallest outline containing it
+    let mut holes: Vec<Vec<usize>> = vec![Vec::new(); loops.len()];
+    for (h, hole) in loops.iter().enumerate().filter(|(_, l)| l.1 < 0.0) {
+        let outer = loops
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| l.1 > 0.0 && contains(&l.0.points_2d, hole.0.points_2d[0]))
+            .min_by(|a, b| a.1 .1.total_cmp(&b.1 .1));
+
+        if let Some((o, _)) = outer {
+            holes[o].push(h);
+        }
+    }
+
+    let mut out = Vec::new();
+    for (o, (outer, _)) in loops.iter().enumerate().filter(|(_, l)| l.1 > 0.0) {
+        let mut points = outer.points.clone();
+        let mut uvs = outer.points_2d.clone();
+        let mut hole_uvs = Vec::new();
+        for &h in &holes[o] {
+            points.extend(&loops[h].0.points);
+            uvs.extend(&loops[h].0.points_2d);
+            hole_uvs.push(loops[h].0.points_2d.clone());
+        }
+
+        for [a, b, c] in triangulate(&outer.points_2d, &hole_uvs) {
+            out.push(Triangle::new(
+                [points[a], points[b], points[c]],
+                [uvs[a], uvs[b], uvs[c]],
+                normal,
+            ));
+        }
+    }
+
+    out
+}
@@ -0,0 +1,236 @@
+use std::{collections::HashMap, fmt};
+
+use glam::Vec3;
+
+use crate::disjoint::DisjointSet;
+
+use super::{IndexError, IndexMesh, TriMesh};
+
+/// The problems found in a mesh by `analyze`,
+/// each with the indices of the offending vertices or faces
+///
+/// edges are pairs of vertex indices,
+/// for a `TriMesh` vertices are equal points numbered in order of first use
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshReport {
+    /// edges used by more than two faces
+    pub non_manifold_edges: Vec<[usize; 2]>,
+    /// vertices whose faces don't form a single fan,
+    /// like the tip of two cones touching
+    pub non_manifold_vertices: Vec<usize>,
+    /// edges used by a single face
+    pub boundary_edges: Vec<[usize; 2]>,
+    /// faces with (almost) zero area
+    pub degenerate_faces: Vec<usize>,
+    /// faces using the same vertices as an earlier face, and that earlier face
+    pub duplicate_faces: Vec<[usize; 2]>,
+    /// edges whose two faces run along them in the same direction,
+    /// so one of them is flipped
+    pub inconsistent_winding: Vec<[usize; 2]>,
+    /// vertices with a NaN or infinite coordinate
+    pub invalid_vertices: Vec<usize>,
+    /// faces with a corner out of range
+    pub out_of_range: Vec<IndexError>,
+}
+
+impl MeshReport {
+    /// returns whether there's nothing wrong with the mesh  
+    /// open boundaries are allowed
+    pub fn is_valid(&self) -> bool {
+        self.non_manifold_edges.is_empty()
+            && self.non_manifold_vertices.is_empty()
+            && self.degenerate_faces.is_empty()
+            && self.duplicate_faces.is_empty()
+            && self.inconsistent_winding.is_empty()
+            && self.invalid_vertices.is_empty()
+            && self.out_of_range.is_empty()
+    }
+
+    fn into_result(self) -> Result<(), Box<MeshReport>> {
+        if self.is_valid() {
+            Ok(())
+        } else {
+            Err(Box::new(self))
+        }
+    }
+
+    /// returns whether the mesh is a valid closed surface
+    pub fn is_watertight(&self) -> bool {
+        self.is_valid() && self.boundary_edges.is_empty()
+    }
+}
+
+impl fmt::Display for MeshReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = [
+            (self.non_manifold_edges.len(), "non manifold edges"),
+            (self.non_manifold_vertices.len(), "non manifold vertices"),
+            (self.boundary_edges.len(), "boundary edges"),
+            (self.degenerate_faces.len(), "degenerate faces"),
+            (self.duplicate_faces.len(), "duplicate faces"),
+            (
+                self.inconsistent_winding.len(),
+                "edges with inconsistent winding",
+            ),
+            (self.invalid_vertices.len(), "invalid vertices"),
+            (self.out_of_range.len(), "faces out of range"),
+        ];
+
+        let problems: Vec<String> = counts
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, name)| format!("{count} {name}"))
+            .collect();
+
+        if problems.is_empty() {
+            write!(f, "no problems")
+        } else {
+            write!(f, "{}", problems.join(", "))
+        }
+    }
+}
+
+impl std::error::Error for MeshReport {}
+
+impl IndexMesh {
+    /// Looks for problems in this mesh
+    pub fn analyze(&self) -> MeshReport {
+        let mut report = MeshReport::default();
+        let mut faces = Vec::with_capacity(self.faces.len());
+        for i in 0..self.faces.len() {
+            match self.check_face(i) {
+                Ok(face) => faces.push((i, face.positions())),
+                Err(e) => report.out_of_range.push(e),
+            }
+        }
+
+        analyze(&self.vertices, &faces, &mut report);
+        report
+    }
+
+    /// returns an error with every problem in this mesh,
+    /// open boundaries are allowed
+    pub fn validate(&self) -> Result<(), Box<MeshReport>> {
+        self.analyze().into_result()
+    }
+}
+
+impl TriMesh {
+    /// Looks for problems in this mesh,
+    /// triangles are connected where their points are equal
+    pub fn analyze(&self) -> MeshReport {
+        let mesh = IndexMesh::from_trimesh(self, 0.0);
+        let faces: Vec<(usize, [usize; 3])> = mesh
+            .faces
+            .iter()
+            .map(|f| f.positions())
+            .enumerate()
+            .collect();
+
+        let mut report = MeshReport::default();
+        analyze(&mesh.vertices, &faces, &mut report);
+        report
+    }
+
+    /// returns an error with every problem in this mesh,
+    /// open boundaries are allowed
+    pub fn validate(&self) -> Result<(), Box<MeshReport>> {
+        self.analyze().into_result()
+    }
+}
+
+/// Fills `report` with the problems of `faces`,
+/// pairs of face indices and their vertices
+fn analyze(vertices: &[Vec3], faces: &[(usize, [usize; 3])], report: &mut MeshReport) {
+    report.invalid_vertices = (0..vertices.len())
+        .filter(|&v| !vertices[v].is_finite())
+        .collect();
+
+    let mut seen: HashMap<[usize; 3], usize> = HashMap::new();
+    let mut used = Vec::with_capacity(faces.len());
+    // the directed uses of every edge, by face
+    let mut edges: HashMap<[usize; 2], Vec<bool>> = HashMap::new();
+    for &(f, corners) in faces {
+        if is_degenerate(corners.map(|c| vertices[c])) {
+            report.degenerate_faces.push(f);
+            continue;
+        }
+
+        let mut sorted = corners;
+        sorted.sort_unstable();
+        if let Some(&first) = seen.get(&sorted) {
+            report.duplicate_faces.push([f, first]);
+            continue;
+        }
+
+        seen.insert(sorted, f);
+        used.push(corners);
+        for i in 0..3 {
+            let (a, b) = (corners[i], corners[(i + 1) % 3]);
+            edges.entry([a.min(b), a.max(b)]).or_default().push(a < b);
+        }
+    }
+
+    let mut keys: Vec<[usize; 2]> = edges.keys().copied().collect();
+    keys.sort_unstable();
+    for edge in keys {
+        match edges[&edge][..] {
+            [_] => report.boundary_edges.push(edge),
+            [a, b] if a == b => report.inconsistent_winding.push(edge),
+            [_, _] => {}
+            _ => report.non_manifold_edges.push(edge),
+        }
+    }
+
+    // a vertex with more than one fan of corners is non manifold
+    let mut groups: Vec<(usize, usize)> = fans(&used)
+        .into_iter()
+        .enumerate()
+        .map(|(c, fan)| (used[c / 3][c % 3], fan))
+        .collect();
+    groups.sort_unstable();
+    groups.dedup();
+
+    report.non_manifold_vertices = groups
+        .windows(2)
+        .filter(|w| w[0].0 == w[1].0)
+        .map(|w| w[0].0)
+        .collect();
+    report.non_manifold_vertices.dedup();
+}
+
+/// returns whether a triangle has (almost) zero area
+/// compared to it's longest edge
+pub(crate) fn is_degenerate(p: [Vec3; 3]) -> bool {
+    let cross = (p[1] - p[0]).cross(p[2] - p[0]);
+    let longest = (0..3)
+        .map(|i| p[i].distance_squared(p[(i + 1) % 3]))
+        .fold(0.0, f32::max);
+
+    cross.length() <= f32::EPSILON * longest
+}
+
+/// Groups the corners of every vertex into fans,
+/// joined by the edges between them  
+/// returns the fan of every corner, `face * 3 + corner`
+pub(crate) fn fans(faces: &[[usize; 3]]) -> Vec<usize> {
+    let mut edges: HashMap<[usize; 2], Vec<usize>> = HashMap::new();
+    for (f, corners) in faces.iter().enumerate() {
+        for i in 0..3 {
+            let (a, b) = (corners[i], corners[(i + 1) % 3]);
+            edges.entry([a.min(b), a.max(b)]).or_default().push(f);
+        }
+    }
+
+    let corner = |f: usize, v: usize| f * 3 + faces[f].iter().position(|&c| c == v).unwrap_or(0);
+    let mut corners = DisjointSet::new(faces.len() * 3);
+    for (edge, uses) in &edges {
+        for w in uses.windows(2) {
+            for &v in edge {
+                corners.union(corner(w[0], v), corner(w[1], v));
+            }
+        }
+    }
+
+    (0..faces.len() * 3).map(|c| corners.find(c)).collect()
+}
@@ -1,10 +1,12 @@
 mod side;
 
-use std::f32::EPSILON;
+use glam::{Vec2, Vec3};
 
-use glam::Vec3;
-
-use crate::{traits::Intersect, Ray, Segment};
+use crate::{
+    hit::RayHit,
+    traits::{Intersect, Raycast},
+    Ray, Segment,
+};
 pub use side::Side;
 
 /// An infinitly large plane
@@ -22,14 +24,46 @@ impl Plane {
     /// Returns which side of the plane, `point` is on
     pub fn side(&self, point: Vec3) -> Side {
         let dot = self.normal.dot(point - self.point);
-        if dot < -EPSILON {
+        if dot < -f32::EPSILON {
             Side::Below
-        } else if dot > EPSILON {
+        } else if dot > f32::EPSILON {
             Side::Above
         } else {
             Side::Coplanar
         }
     }
+
+    /// returns where the line through `a` and `b` crosses this plane
+    ///
+    /// always interpolates from the lesser point
+    /// so both triangles sharing an edge get the same point
+    pub(crate) fn cross_point(&self, a: Vec3, b: Vec3) -> Vec3 {
+        let (a, b) = if a.to_array() <= b.to_array() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let vector = b - a;
+        let t = self.normal.dot(self.point - a) / self.normal.dot(vector);
+        a + vector * t
+    }
+
+    /// returns two unit vectors along the plane  
+    /// where `u.cross(v)` points along the normal
+    pub fn basis(&self) -> (Vec3, Vec3) {
+        let n = self.normal.normalize();
+        let other = if n.x.abs() < 0.9 { Vec3::X } else { Vec3::Y };
+        let u = other.cross(n).normalize();
+        (u, n.cross(u))
+    }
+
+    /// returns the position of `point` along the plane's `basis`  
+    /// relative to `self.point`
+    pub fn project(&self, point: Vec3) -> Vec2 {
+        let (u, v) = self.basis();
+        let d = point - self.point;
+        Vec2::new(d.dot(u), d.dot(v))
+    }
 }
 
 impl Intersect<Segment, Option<Vec3>> for Plane {
@@ -45,7 +79,7 @@ impl Intersect<Segment, Option<Vec3>> for Plane {
         }
 
         let t = (d - self.normal.dot(segment[0])) / self.normal.dot(ray);
-        if t >= 0.0 && t <= 1.0 {
+        if (0.0..=1.0).contains(&t) {
             return Some(segment[0] + ray * t);
         }
 
@@ -59,9 +93,9 @@ impl Intersect<Ray, Option<Vec3>> for Plane {
     fn intersects(&self, ray: &Ray) -> Option<Vec3> {
         // https://stackoverflow.com/a/23976134
         let denom = self.normal.dot(ray.1);
-        if denom.abs() > EPSILON {
+        if denom.abs() > f32::EPSILON {
             let t = (self.point - ray.0).dot(self.normal) / denom;
-            if t < EPSILON {
+            if t < f32::EPSILON {
                 return None;
             }
 
@@ -71,3 +105,28 @@ impl Intersect<Ray, Option<Vec3>> for Plane {
         }
     }
 }
+
+impl Raycast for Plane {
+    /// get the hit record of a ray  
+    /// returns None if there's no intersection in range
+    fn raycast_range(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<RayHit> {
+        let denom = self.normal.dot(ray.1);
+        if denom.abs() <= f32::EPSILON {
+            return None;
+        }
+
+        let t = (self.point - ray.0).dot(self.normal) / denom;
+        if t < t_min || t > t_max {
+            return None;
+        }
+
+        Some(RayHit {
+            point: ray.0 + t * ray.1,
+            distance: t,
+            barycentric: Vec3::ZERO,
+            front_face: denom < 0.0,
+            uv: Vec2::ZERO,
+            normal: self.normal,
+        })
+    }
+}
@@ -1,22 +1,31 @@
 mod modifiers;
 
-use std::{
-    f32::EPSILON,
-    ops::{Index, IndexMut},
-};
+use std::ops::{Index, IndexMut};
 
-use glam::Vec3;
+use glam::{Vec2, Vec3};
 
-use crate::{plane::Plane, traits::Intersect, triangle::Triangle, vertex::Vertex, Ray};
+use crate::{
+    hit::RayHit,
+    plane::Plane,
+    traits::{Intersect, Raycast},
+    triangle::Triangle,
+    vertex::Vertex,
+    Ray,
+};
 
 #[derive(Clone, Debug, Default)]
 pub struct Polygon {
     pub vertices: Vec<Vertex>,
+    /// the material or group this polygon belongs to
+    pub material: u32,
 }
 
 impl Polygon {
     pub fn new(vertices: Vec<Vertex>) -> Self {
-        Self { vertices }
+        Self {
+            vertices,
+            material: 0,
+        }
     }
 
     pub fn add(&mut self, vertex: Vertex) {
@@ -64,6 +73,7 @@ impl Polygon {
 
     pub fn flip(&self) -> Polygon {
         let mut output = Polygon::new(Vec::new());
+        output.material = self.material;
         for i in 1..=self.len() {
             let i = self.len() - i;
             output.add(Vertex::new(self[i].point, self[i].uv, -self[i].normal));
@@ -95,6 +105,7 @@ impl From<Triangle> for Polygon {
                 Vertex::new(value[1], value.uvs[1], value.normal),
                 Vertex::new(value[2], value.uvs[2], value.normal),
             ],
+            material: value.material,
         }
     }
 }
@@ -102,17 +113,14 @@ impl From<Triangle> for Polygon {
 impl Intersect<Ray, Option<Vec3>> for Polygon {
     fn intersects(&self, other: &Ray) -> Option<Vec3> {
         let plane = Plane::new(self[0].point, self[0].normal);
-        let point = match plane.intersects(other) {
-            Some(point) => point,
-            None => return None,
-        };
+        let point = plane.intersects(other)?;
 
         for i in 0..self.len() {
             let j = (i + 1) % self.len();
             let n12 = (self[j].point - self[i].point).cross(self[0].normal);
 
             let da = (point - self[i].point).dot(n12) / n12.length();
-            if da >= -EPSILON {
+            if da >= -f32::EPSILON {
                 return None;
             }
         }
@@ -120,3 +128,33 @@ impl Intersect<Ray, Option<Vec3>> for Polygon {
         Some(point)
     }
 }
+
+impl Raycast for Polygon {
+    /// get the hit record of a ray
+    /// by testing every triangle in the polygon's fan  
+    /// returns None if there's no intersection in range
+    fn raycast_range(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<RayHit> {
+        let mut closest: Option<RayHit> = None;
+        for i in 1..self.len().saturating_sub(1) {
+            let [v0, v1, v2] = [self[0], self[i], self[i + 1]];
+            let triangle =
+                Triangle::new([v0.point, v1.point, v2.point], [Vec2::ZERO; 3], Vec3::ZERO);
+            let t_max = closest.map_or(t_max, |hit| hit.distance);
+            let hit = match triangle.raycast_range(ray, t_min, t_max) {
+                Some(hit) => hit,
+                None => continue,
+            };
+
+            let b = hit.barycentric;
+            let normal = v0.normal * b.x + v1.normal * b.y + v2.normal * b.z;
+            closest = Some(RayHit {
+                front_face: ray.1.dot(normal) < 0.0,
+                uv: v0.uv * b.x + v1.uv * b.y + v2.uv * b.z,
+                normal,
+                ..hit
+            });
+        }
+
+        closest
+    }
+}
@@ -46,12 +46,17 @@ impl Polygon {
             last = si;
         }
 
+        let part = |vertices| Polygon {
+            vertices,
+            material: self.material,
+        };
+
         if vabove.len() > 2 {
-            above.push(Polygon::new(vabove));
+            above.push(part(vabove));
         }
 
         if vbelow.len() > 2 {
-            below.push(Polygon::new(vbelow));
+            below.push(part(vbelow));
         }
     }
 }
@@ -0,0 +1,101 @@
+use glam::{Vec2, Vec3};
+
+use super::shapes::{random, wavy_grid};
+use crate::{bvh::Bvh, meshes::TriMesh, traits::Intersect, triangle::Triangle, Ray};
+
+#[test]
+pub fn closest_hit() {
+    let mesh = wavy_grid(32);
+    let bvh = Bvh::new(&mesh);
+
+    let mut seed = 7;
+    for _ in 0..200 {
+        let origin = Vec3::new(
+            4.0 + random(&mut seed) * 24.0,
+            5.0,
+            4.0 + random(&mut seed) * 24.0,
+        );
+        let direction = Vec3::new(random(&mut seed) - 0.5, -1.0, random(&mut seed) - 0.5);
+        let ray: Ray = (origin, direction);
+
+        let (triangle, hit) = bvh.closest_hit(&ray).unwrap();
+        let brute = mesh
+            .triangles
+            .iter()
+            .filter_map(|t| t.intersects(&ray))
+            .map(|p| p.distance(ray.0) / ray.1.length())
+            .fold(f32::INFINITY, f32::min);
+
+        assert!((hit.distance - brute).abs() < 1e-4);
+        assert!(bvh.any_hit(&ray));
+
+        let point = ray.0 + ray.1 * hit.distance;
+        let t = mesh[triangle];
+        let b = hit.barycentric;
+        assert!(point.distance(t[0] * b.x + t[1] * b.y + t[2] * b.z) < 1e-4);
+    }
+
+    let up: Ray = (Vec3::new(4.0, 5.0, 4.0), Vec3::Y);
+    assert_eq!(bvh.closest_hit(&up), None);
+    assert!(!bvh.any_hit(&up));
+}
+
+#[test]
+pub fn segment_hit() {
+    let mesh = TriMesh::new(vec![Triangle::new(
+        [Vec3::ZERO, Vec3::new(0.0, 0.0, 1.0), Vec3::X],
+        [Vec2::ZERO; 3],
+        Vec3::Y,
+    )]);
+    let bvh = Bvh::new(&mesh);
+
+    let (_, hit) = bvh
+        .segment_hit(&[Vec3::new(0.25, 1.0, 0.25), Vec3::new(0.25, -1.0, 0.25)])
+        .unwrap();
+    assert_eq!(hit.distance, 0.5);
+    assert_eq!(hit.barycentric, Vec3::new(0.5, 0.25, 0.25));
+
+    let miss = bvh.segment_hit(&[Vec3::new(0.25, 2.0, 0.25), Vec3::new(0.25, 1.0, 0.25)]);
+    assert_eq!(miss, None);
+}
+
+#[test]
+pub fn overlaps() {
+    let mesh = wavy_grid(8);
+    let bvh = Bvh::new(&mesh);
+
+    let found = bvh.overlaps(Vec3::new(2.5, -10.0, 2.5), Vec3::new(3.5, 10.0, 2.6));
+    let brute: Vec<usize> = (0..mesh.tricount())
+        .filter(|&i| {
+            let t = mesh[i];
+            t.min().x <= 3.5 && t.max().x >= 2.5 && t.min().z <= 2.6 && t.max().z >= 2.5
+        })
+        .collect();
+
+    assert_eq!(found, brute);
+    assert_eq!(found.len(), 4);
+}
+
+#[test]
+pub fn closest_point() {
+    let mesh = wavy_grid(16);
+    let bvh = Bvh::new(&mesh);
+
+    let mut seed = 3;
+    for _ in 0..200 {
+        let point = Vec3::new(random(&mut seed), random(&mut seed), random(&mut seed)) * 20.0
+            - Vec3::new(2.0, 10.0, 2.0);
+
+        let expected = mesh
+            .triangles
+            .iter()
+            .map(|t| t.closest_point(point).distance(point))
+            .fold(f32::INFINITY, f32::min);
+        let (i, closest) = bvh.closest_point(point).unwrap();
+
+        assert!((closest.distance(point) - expected).abs() < 1e-4);
+        assert_eq!(mesh.triangles[i].closest_point(point), closest);
+    }
+
+    assert!(Bvh::default().closest_point(Vec3::ZERO).is_none());
+}
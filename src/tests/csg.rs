@@ -0,0 +1,63 @@
+use glam::{Vec2, Vec3};
+
+use super::shapes::cube;
+use crate::meshes::{PolyMesh, TriMesh};
+
+fn cubes() -> (TriMesh, TriMesh) {
+    (cube(Vec3::ZERO, 2.0), cube(Vec3::ONE, 2.0))
+}
+
+#[test]
+pub fn union() {
+    let (mut a, b) = cubes();
+    a.attributes
+        .insert("weight", vec![1.0_f32; 3 * a.tricount()]);
+    let mesh = a.union(&b);
+
+    assert!((mesh.volume() - 15.0).abs() < 1e-4);
+    // attributes don't survive the split
+    assert!(mesh.attributes.is_empty());
+}
+
+#[test]
+pub fn difference() {
+    let (a, b) = cubes();
+    let mesh = a.difference(&b);
+
+    assert!((mesh.volume() - 7.0).abs() < 1e-4);
+    assert!((b.difference(&a).volume() - 7.0).abs() < 1e-4);
+}
+
+#[test]
+pub fn intersection() {
+    let (a, b) = cubes();
+    let mesh = a.intersection(&b);
+
+    assert!((mesh.volume() - 1.0).abs() < 1e-4);
+    let [min, max] = mesh.min_max().unwrap();
+    assert!(min.abs_diff_eq(Vec3::ZERO, 1e-5));
+    assert!(max.abs_diff_eq(Vec3::ONE, 1e-5));
+}
+
+#[test]
+pub fn keeps_attributes() {
+    let (mut a, b) = cubes();
+    for t in &mut a.triangles {
+        t.uvs = [Vec2::ONE; 3];
+    }
+
+    let mesh = PolyMesh::from(a).difference(&b.into());
+    let mut uvs = [false; 2];
+    for polygon in &mesh.polygons {
+        let uv = polygon[0].uv;
+        assert!(polygon.vertices.iter().all(|v| v.uv == uv));
+        uvs[uv.x as usize] = true;
+
+        // faces from `b` are flipped along with their normals
+        let winding =
+            (polygon[1].point - polygon[0].point).cross(polygon[2].point - polygon[0].point);
+        assert!(polygon.vertices.iter().all(|v| v.normal.dot(winding) > 0.0));
+    }
+
+    assert_eq!(uvs, [true, true]);
+}
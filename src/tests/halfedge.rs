@@ -0,0 +1,188 @@
+use glam::{Vec2, Vec3};
+
+use super::shapes::{cube, quad_cube};
+use crate::{
+    hull::convex_hull,
+    meshes::{Corner, HalfEdgeError, HalfEdgeMesh, IndexMesh, PolyMesh, TriMesh, VertexId},
+};
+
+/// Checks the links between half edges are consistent
+fn check(mesh: &HalfEdgeMesh) {
+    for h in mesh.half_edge_ids() {
+        assert_eq!(mesh.twin(mesh.twin(h)), h);
+        assert_eq!(mesh.next(mesh.prev(h)), h);
+        assert_eq!(mesh.origin(mesh.next(h)), mesh.destination(h));
+        assert_eq!(mesh.face(mesh.next(h)), mesh.face(h));
+        assert!(!mesh.is_half_edge_removed(mesh.next(h)));
+        assert!(!mesh.is_vertex_removed(mesh.origin(h)));
+    }
+
+    for v in mesh.vertex_ids() {
+        let count = mesh.vertex_half_edges(v).count();
+        let all = mesh
+            .half_edge_ids()
+            .filter(|&h| mesh.origin(h) == v)
+            .count();
+        assert_eq!(count, all);
+    }
+}
+
+/// a unit square split into two triangles
+fn square() -> IndexMesh {
+    let mut mesh = IndexMesh::default();
+    let n = mesh.add_normal(Vec3::Z);
+    let c: Vec<Corner> = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]
+        .into_iter()
+        .map(|p| Corner::new(mesh.add_vertex(p.extend(0.0)), mesh.add_uv(p), n))
+        .collect();
+    mesh.add_triangle(c[0], c[1], c[2]);
+    mesh.add_triangle(c[0], c[2], c[3]);
+    mesh
+}
+
+#[test]
+pub fn conversions() {
+    let mesh = HalfEdgeMesh::try_from(cube(Vec3::ZERO, 2.0)).unwrap();
+    check(&mesh);
+    assert_eq!(
+        (mesh.vertex_count(), mesh.edge_count(), mesh.face_count()),
+        (8, 18, 12)
+    );
+    assert!(mesh.boundary_loops().is_empty());
+    assert!((TriMesh::from(mesh).volume() - 8.0).abs() < 1e-5);
+
+    let mesh = HalfEdgeMesh::try_from(quad_cube(Vec3::ZERO, 2.0)).unwrap();
+    check(&mesh);
+    assert_eq!(
+        (mesh.vertex_count(), mesh.edge_count(), mesh.face_count()),
+        (8, 12, 6)
+    );
+    for f in mesh.face_ids() {
+        assert_eq!(mesh.face_neighbours(f).count(), 4);
+    }
+
+    let polygons = PolyMesh::from(mesh);
+    assert_eq!(polygons.polycount(), 6);
+    assert!(polygons.polygons.iter().all(|p| p.len() == 4));
+
+    let index = IndexMesh::from(HalfEdgeMesh::try_from(square()).unwrap());
+    assert_eq!(index.faces, square().faces);
+}
+
+#[test]
+pub fn topology() {
+    let mesh = HalfEdgeMesh::try_from(square()).unwrap();
+    check(&mesh);
+
+    let loops = mesh.boundary_loops();
+    assert_eq!(loops.len(), 1);
+    assert_eq!(loops[0].len(), 4);
+
+    let (v0, v2) = (VertexId(0), VertexId(2));
+    assert!(mesh.is_boundary_vertex(v0));
+    assert_eq!(mesh.vertex_ring(v0).count(), 3);
+    assert_eq!(mesh.vertex_faces(v0).count(), 2);
+
+    let diagonal = mesh.find_half_edge(v0, v2).unwrap();
+    assert!(!mesh.is_boundary_edge(diagonal));
+    assert!(mesh.edge_faces(diagonal).iter().all(Option::is_some));
+    assert_eq!(mesh.uv(mesh.twin(diagonal)), Vec2::ONE);
+}
+
+#[test]
+pub fn non_manifold() {
+    let mut mesh = square();
+    let c = mesh.faces[0].corners;
+    let top = Corner::new(mesh.add_vertex(Vec3::Z), 0, 0);
+    mesh.add_triangle(c[0], c[1], top);
+
+    assert_eq!(
+        HalfEdgeMesh::try_from(mesh).unwrap_err(),
+        HalfEdgeError::NonManifold {
+            face: 2,
+            edge: [0, 1]
+        }
+    );
+}
+
+#[test]
+pub fn edits() {
+    let mut mesh = HalfEdgeMesh::try_from(cube(Vec3::ZERO, 2.0)).unwrap();
+    let diagonal = mesh
+        .half_edge_ids()
+        .find(|&h| {
+            let d = mesh.position(mesh.origin(h)) - mesh.position(mesh.destination(h));
+            d.abs().cmpgt(Vec3::splat(0.5)).bitmask().count_ones() == 2
+        })
+        .unwrap();
+
+    assert!(mesh.flip_edge(diagonal));
+    check(&mesh);
+    assert_eq!(mesh.face_count(), 12);
+
+    let edge = mesh.half_edge_ids().find(|&h| h != diagonal).unwrap();
+    let middle = (mesh.position(mesh.origin(edge)) + mesh.position(mesh.destination(edge))) * 0.5;
+    let vertex = mesh.split_edge(edge, middle);
+    check(&mesh);
+    assert_eq!(
+        (mesh.vertex_count(), mesh.edge_count(), mesh.face_count()),
+        (9, 21, 14)
+    );
+    assert_eq!(mesh.vertex_ring(vertex).count(), 4);
+
+    // collapsing the new vertex back keeps the cube's shape
+    let back = mesh.vertex_half_edges(vertex).next().unwrap();
+    let kept = mesh.collapse_edge(mesh.twin(back)).unwrap();
+    check(&mesh);
+    assert!(mesh.is_vertex_removed(vertex));
+    assert!(!mesh.is_vertex_removed(kept));
+    assert_eq!(
+        (mesh.vertex_count(), mesh.edge_count(), mesh.face_count()),
+        (8, 18, 12)
+    );
+    assert!((TriMesh::from(mesh).volume() - 8.0).abs() < 1e-5);
+}
+
+#[test]
+pub fn collapse_closed() {
+    // two triangles back to back
+    let mut pillow = IndexMesh::default();
+    let (uv, n) = (pillow.add_uv(Vec2::ZERO), pillow.add_normal(Vec3::Z));
+    let c: Vec<Corner> = [Vec3::ZERO, Vec3::X, Vec3::Y]
+        .into_iter()
+        .map(|p| Corner::new(pillow.add_vertex(p), uv, n))
+        .collect();
+    pillow.add_triangle(c[0], c[1], c[2]);
+    pillow.add_triangle(c[0], c[2], c[1]);
+
+    let mut mesh = HalfEdgeMesh::try_from(pillow).unwrap();
+    for h in mesh.half_edge_ids().collect::<Vec<_>>() {
+        assert_eq!(mesh.collapse_edge(h), None);
+    }
+    check(&mesh);
+    assert_eq!((mesh.vertex_count(), mesh.face_count()), (3, 2));
+
+    let tetrahedron = convex_hull(&[Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z]).unwrap();
+    let mut mesh = HalfEdgeMesh::try_from(tetrahedron).unwrap();
+    for h in mesh.half_edge_ids().collect::<Vec<_>>() {
+        assert_eq!(mesh.collapse_edge(h), None);
+    }
+    check(&mesh);
+    assert_eq!((mesh.vertex_count(), mesh.face_count()), (4, 4));
+}
+
+#[test]
+pub fn collapse_boundary() {
+    let mut mesh = HalfEdgeMesh::try_from(square()).unwrap();
+    let (v0, v1, v2) = (VertexId(0), VertexId(1), VertexId(2));
+
+    // the diagonal connects two boundary vertices
+    let diagonal = mesh.find_half_edge(v0, v2).unwrap();
+    assert_eq!(mesh.collapse_edge(diagonal), None);
+
+    let edge = mesh.find_half_edge(v0, v1).unwrap();
+    assert_eq!(mesh.collapse_edge(edge), Some(v0));
+    check(&mesh);
+    assert_eq!(mesh.face_count(), 1);
+    assert_eq!(mesh.boundary_loops()[0].len(), 3);
+}
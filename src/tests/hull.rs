@@ -0,0 +1,129 @@
+use glam::{Vec2, Vec3};
+
+use super::shapes::{cube, prism, random, sphere};
+use crate::{
+    hull::{convex_hull, DecomposeOptions},
+    meshes::TriMesh,
+};
+
+#[test]
+pub fn convex_hull_cube() {
+    let mut points: Vec<Vec3> = cube(Vec3::ZERO, 2.0)
+        .triangles
+        .iter()
+        .flat_map(|t| t.points)
+        .collect();
+
+    // points inside, on the faces and duplicates
+    let mut seed = 1;
+    for _ in 0..100 {
+        let p = Vec3::new(random(&mut seed), random(&mut seed), random(&mut seed)) * 2.0 - 1.0;
+        points.push(p);
+        points.push(Vec3::new(1.0, p.y, p.z));
+    }
+
+    let hull = convex_hull(&points).unwrap();
+    assert_eq!(hull.tricount(), 12);
+    assert!((hull.volume() - 8.0).abs() < 1e-5);
+    for t in &hull.triangles {
+        let center = (t[0] + t[1] + t[2]) / 3.0;
+        assert!(t.normal.dot(center) > 0.0);
+    }
+}
+
+#[test]
+pub fn convex_hull_sphere() {
+    let mut seed = 3;
+    let points: Vec<Vec3> = (0..500)
+        .map(|_| {
+            let p = Vec3::new(random(&mut seed), random(&mut seed), random(&mut seed)) * 2.0 - 1.0;
+            p.normalize()
+        })
+        .collect();
+
+    let hull = convex_hull(&points).unwrap();
+    // a closed triangle mesh has 2V - 4 triangles
+    let vertices = hull.tricount() / 2 + 2;
+    assert_eq!(vertices, 500);
+    for p in &points {
+        for t in &hull.triangles {
+            assert!(t.normal.dot(*p - t[0]) < 1e-5);
+        }
+    }
+}
+
+#[test]
+pub fn convex_hull_flat() {
+    let points = [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::new(1.0, 1.0, 0.0)];
+    assert!(convex_hull(&points).is_none());
+    assert!(convex_hull(&[]).is_none());
+}
+
+#[test]
+pub fn convex_decomposition() {
+    // an L shaped prism with a volume of 3
+    let outline = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(1.0, 2.0),
+        Vec2::new(0.0, 2.0),
+    ];
+    let mesh = prism(&outline, 1.0);
+    assert!((mesh.volume() - 3.0).abs() < 1e-5);
+
+    let hulls = mesh.convex_decomposition(DecomposeOptions::default());
+    assert!(hulls.len() >= 2 && hulls.len() <= 4);
+
+    let volume: f32 = hulls.iter().map(|h| h.volume()).sum();
+    // the hulls only cover the mesh's concavity within the tolerance
+    let tolerance = DecomposeOptions::default().concavity * mesh.convex_hull().unwrap().volume();
+    assert!(volume > 3.0 - 1e-4 && volume < 3.0 + tolerance);
+    for hull in &hulls {
+        assert!(hull.convex_hull().unwrap().volume() - hull.volume() < 1e-4);
+    }
+}
+
+#[test]
+pub fn convex_decomposition_limits() {
+    let mesh = cube(Vec3::ZERO, 2.0);
+    let options = DecomposeOptions {
+        max_hull_vertices: 4,
+        ..Default::default()
+    };
+
+    let hulls = mesh.convex_decomposition(options);
+    assert_eq!(hulls.len(), 1);
+    assert_eq!(hulls[0].tricount(), 4);
+    assert!(encloses(&hulls, &mesh));
+    assert!(hulls[0].volume() > 8.0);
+}
+
+#[test]
+pub fn convex_decomposition_encloses() {
+    let mesh = sphere(8, 16);
+    let options = DecomposeOptions {
+        max_hull_vertices: 12,
+        ..Default::default()
+    };
+
+    let hulls = mesh.convex_decomposition(options);
+    assert!(hulls.len() > 1);
+    assert!(encloses(&hulls, &mesh));
+    for hull in &hulls {
+        // a closed triangulated hull has 2 * vertices - 4 triangles
+        assert!(hull.tricount() / 2 + 2 <= 12);
+    }
+}
+
+/// returns whether every point of `mesh` is inside one of `hulls`
+fn encloses(hulls: &[TriMesh], mesh: &TriMesh) -> bool {
+    mesh.triangles.iter().flat_map(|t| t.points).all(|p| {
+        hulls.iter().any(|hull| {
+            hull.triangles
+                .iter()
+                .all(|t| t.normal.dot(p - t[0]) <= 1e-4)
+        })
+    })
+}
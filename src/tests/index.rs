@@ -0,0 +1,592 @@
+use std::collections::HashMap;
+
+use glam::{Vec2, Vec3, Vec4};
+
+use super::shapes::{cube, seam_grid, sphere};
+use crate::{
+    meshes::{
+        Attribute, Corner, FillOptions, IndexError, IndexMesh, LoopOptions, NormalOptions,
+        NormalWeighting, RepairChange, RepairOptions, SeparateOptions, SimplifyOptions, TriMesh,
+    },
+    triangle::Triangle,
+};
+
+fn two_quads(offset: Vec3) -> IndexMesh {
+    let mut mesh = IndexMesh::default();
+    let uv = mesh.add_uv(Vec2::ZERO);
+    let n = mesh.add_normal(Vec3::Z);
+    let c = |v| Corner::new(v, uv, n);
+    for x in [0.0, 2.0 + offset.x] {
+        let base = Vec3::new(x, 0.0, 0.0);
+        let v0 = mesh.add_vertex(base);
+        let v1 = mesh.add_vertex(base + Vec3::X);
+        let v2 = mesh.add_vertex(base + Vec3::new(1.0, 1.0, 0.0));
+        let v3 = mesh.add_vertex(base + Vec3::Y);
+        mesh.add_triangle(c(v0), c(v1), c(v2));
+        mesh.add_triangle(c(v0), c(v2), c(v3));
+    }
+
+    mesh
+}
+
+#[test]
+pub fn separate_by_loose_parts() {
+    let parts = two_quads(Vec3::ZERO).separate_by_loose_parts().unwrap();
+
+    assert_eq!(parts.len(), 2);
+    for part in &parts {
+        assert_eq!(part.vertices.len(), 4);
+        assert_eq!(part.facecount(), 2);
+        assert_eq!((part.uvs.len(), part.normals.len()), (1, 1));
+    }
+
+    assert_eq!(parts[1].vertices[0], Vec3::new(2.0, 0.0, 0.0));
+}
+
+#[test]
+pub fn separate_by_loose_parts_tolerance() {
+    // the second quad starts 0.001 away from the first quad's corner
+    let mesh = two_quads(Vec3::new(-0.999, 0.0, 0.0));
+
+    let parts = mesh.clone().separate_by_loose_parts().unwrap();
+    assert_eq!(parts.len(), 2);
+
+    let parts = mesh
+        .separate_by_loose_parts_with(SeparateOptions {
+            tolerance: Some(0.01),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].vertices.len(), 8);
+}
+
+#[test]
+pub fn from_trimesh() {
+    let mesh = TriMesh::new(vec![
+        Triangle::from_points([Vec3::ZERO, Vec3::X, Vec3::Y]),
+        Triangle::from_points([Vec3::X, Vec3::new(1.0, 1.0, 0.0), Vec3::Y]),
+        Triangle::from_points([
+            Vec3::new(1.00001, 1.0, 0.0),
+            Vec3::new(2.0, 1.0, 0.0),
+            Vec3::new(1.0, 2.0, 0.0),
+        ]),
+    ]);
+
+    let exact = IndexMesh::from(mesh.clone());
+    assert_eq!(exact.vertices.len(), 7);
+    assert_eq!(exact.uvs.len(), 1);
+    assert_eq!(exact[1][0], Corner::new(1, 0, 0));
+    assert_eq!(exact[2][0], Corner::new(4, 0, 1));
+
+    let welded = IndexMesh::from_trimesh(&mesh, 0.001);
+    assert_eq!(welded.vertices.len(), 6);
+}
+
+#[test]
+pub fn weld() {
+    let mut mesh = two_quads(Vec3::new(-0.9999, 0.0, 0.0));
+    mesh.weld(0.001).unwrap();
+
+    assert_eq!(mesh.vertices.len(), 6);
+    assert_eq!(mesh[2][0].position, 1);
+    assert_eq!(mesh[3][2].position, 2);
+    assert_eq!(mesh.separate_by_loose_parts().unwrap().len(), 1);
+}
+
+#[test]
+pub fn weld_out_of_range() {
+    let mut mesh = two_quads(Vec3::ZERO);
+    mesh[3][1].position = 8;
+
+    assert!(mesh.weld(0.001).is_err());
+    assert_eq!(mesh.vertices.len(), 8);
+    assert!(mesh.separate_by_loose_parts().is_err());
+}
+
+#[test]
+pub fn triangle_out_of_range() {
+    let mut mesh = two_quads(Vec3::ZERO);
+    mesh[1][2].uv = 4;
+
+    assert!(mesh.triangle(0).is_ok());
+    assert_eq!(
+        mesh.triangle(1).unwrap_err(),
+        IndexError::OutOfRange {
+            face: 1,
+            corner: 2,
+            attribute: Attribute::Uv,
+            index: 4,
+            len: 1,
+        }
+    );
+    assert!(TriMesh::try_from(mesh).is_err());
+}
+
+#[test]
+pub fn vertex_buffer() {
+    let mut mesh = two_quads(Vec3::ZERO);
+    let uv = mesh.add_uv(Vec2::ONE);
+    mesh[1][1].uv = uv;
+
+    let buffer = mesh.vertex_buffer::<u16>().unwrap();
+    assert_eq!(buffer.vertices.len(), 9);
+    assert_eq!(buffer.indices[..6], [0, 1, 2, 0, 3, 4]);
+    assert_eq!(buffer.vertices[3].uv, Vec2::ONE);
+}
+
+#[test]
+pub fn simplify() {
+    let mesh = seam_grid(8);
+    let options = SimplifyOptions {
+        target_triangles: 8,
+        max_error: 1e-3,
+        ..Default::default()
+    };
+    let simple = mesh.simplify(options);
+
+    assert!(simple.facecount() < mesh.facecount() / 4);
+    assert_eq!(simple.vertices, mesh.vertices);
+
+    let mut area = 0.0;
+    for face in &simple.faces {
+        let p = face.positions().map(|p| simple.vertices[p]);
+        let normal = (p[1] - p[0]).cross(p[2] - p[0]);
+        assert!(normal.z > 0.0);
+        area += normal.length() * 0.5;
+
+        // no collapse may cross the seam in the middle
+        let center = face
+            .positions()
+            .map(|p| simple.vertices[p].x)
+            .iter()
+            .sum::<f32>()
+            / 3.0;
+        let offset = if center < 0.5 {
+            Vec2::ZERO
+        } else {
+            Vec2::X * 10.0
+        };
+        for c in face.corners {
+            let uv = simple.vertices[c.position].truncate() + offset;
+            assert!(simple.uvs[c.uv].abs_diff_eq(uv, 1e-6));
+        }
+    }
+
+    assert!((area - 1.0).abs() < 1e-4);
+}
+
+#[test]
+pub fn simplify_limits() {
+    let mesh = seam_grid(4);
+    let locked = mesh.simplify(SimplifyOptions {
+        lock_boundary: true,
+        ..Default::default()
+    });
+    assert!(locked.facecount() < mesh.facecount());
+    for (i, v) in mesh.vertices.iter().enumerate() {
+        let boundary = [v.x, v.y].iter().any(|&x| x == 0.0 || x == 1.0);
+        let used = locked.faces.iter().any(|f| f.positions().contains(&i));
+        assert!(used || !boundary);
+    }
+
+    let mut bumpy = seam_grid(4);
+    bumpy.vertices[6].z = 0.5;
+    let options = SimplifyOptions {
+        max_error: 0.01,
+        ..Default::default()
+    };
+    let simple = bumpy.simplify(options);
+    assert!(simple.facecount() < bumpy.facecount());
+    assert!(simple.faces.iter().any(|f| f.positions().contains(&6)));
+}
+
+#[test]
+pub fn simplify_scale() {
+    // scaled by powers of two so the rounding is the same at every size
+    let counts: Vec<usize> = [1.0, 8.0, 64.0]
+        .into_iter()
+        .map(|scale| {
+            let mut mesh = IndexMesh::from_trimesh(&sphere(24, 48), 0.0);
+            for v in &mut mesh.vertices {
+                *v *= scale;
+            }
+
+            let max_error = 0.01 * scale;
+            let simple = mesh.simplify(SimplifyOptions {
+                max_error,
+                ..Default::default()
+            });
+
+            // the vertices stay on the sphere, the faces between them may not sink further
+            for face in &simple.faces {
+                let center = face
+                    .positions()
+                    .map(|p| simple.vertices[p])
+                    .iter()
+                    .sum::<Vec3>()
+                    / 3.0;
+                assert!(scale - center.length() <= max_error);
+            }
+
+            simple.facecount()
+        })
+        .collect();
+
+    assert!(counts[0] < 2208 && counts[0] > 1000);
+    assert!(counts.iter().all(|&c| c == counts[0]));
+}
+
+#[test]
+pub fn lods() {
+    let mut mesh = seam_grid(8);
+    for v in &mut mesh.vertices {
+        v.z = (v.x * 3.0).sin() * 0.1;
+    }
+
+    let lods = mesh.lods(&[1.0, 0.5, 0.25], SimplifyOptions::default());
+    let counts: Vec<usize> = lods.levels.iter().map(|l| l.faces.len()).collect();
+
+    assert_eq!(counts[0], mesh.facecount());
+    assert!(lods.levels[0].error < 1e-6);
+    assert!(counts[1] <= 64 && counts[2] <= 32);
+    assert!(lods.levels[1].error > 0.0);
+    assert!(lods.levels[2].error > 0.0 && lods.levels[2].error < 0.2);
+    assert_eq!(lods.level(2).faces, lods.levels[2].faces);
+
+    let (buffer, ranges) = lods.vertex_buffer::<u16>().unwrap();
+    let own = mesh.vertex_buffer::<u16>().unwrap();
+    assert_eq!(buffer.vertices.len(), own.vertices.len());
+    assert_eq!(ranges[0], 0..counts[0] * 3);
+    assert_eq!(ranges[2].len(), counts[2] * 3);
+    assert_eq!(ranges[2].end, buffer.indices.len());
+}
+
+#[test]
+pub fn loop_subdivide_adaptive() {
+    // stretch the right half so only its triangles are subdivided
+    let mut mesh = seam_grid(4);
+    for v in &mut mesh.vertices {
+        v.x = v.x.min(0.5) + (v.x - 0.5).max(0.0) * 8.0;
+    }
+
+    let options = LoopOptions {
+        max_edge_length: Some(0.5),
+        ..Default::default()
+    };
+    let smooth = mesh.loop_subdivide(&options);
+
+    for (a, b) in mesh.vertices.iter().zip(&smooth.vertices) {
+        assert!(a == b || a.x > 0.5);
+    }
+
+    let mut edges = HashMap::new();
+    for face in &smooth.faces {
+        let [a, b, c] = face.positions();
+        for edge in [(a, b), (b, c), (c, a)] {
+            *edges
+                .entry((edge.0.min(edge.1), edge.0.max(edge.1)))
+                .or_insert(0) += 1;
+        }
+    }
+
+    // 16 boundary edges, 8 of them split in two
+    assert_eq!(edges.values().filter(|&&n| n == 1).count(), 24);
+    assert!(edges.values().all(|&n| n <= 2));
+    // 16 subdivided triangles, 4 of the 16 left ones split in two next to them
+    assert_eq!(smooth.facecount(), 16 * 4 + 12 + 4 * 2);
+}
+
+#[test]
+pub fn analyze() {
+    let mut mesh = seam_grid(2);
+    assert!(mesh.validate().is_ok());
+    assert_eq!(mesh.analyze().boundary_edges.len(), 8);
+
+    // a flap on the first diagonal and a corner out of range
+    let c = mesh.faces[0].corners;
+    let flap = Corner::new(mesh.add_vertex(Vec3::Z), 0, 0);
+    mesh.add_triangle(c[2], c[0], flap);
+    mesh.add_triangle(c[0], c[1], Corner::new(99, 0, 0));
+    mesh.vertices[8].x = f32::NAN;
+
+    let report = mesh.analyze();
+    assert_eq!(report.non_manifold_edges, vec![[0, 4]]);
+    assert_eq!(report.invalid_vertices, vec![8]);
+    assert_eq!(report.out_of_range.len(), 1);
+    assert!(matches!(
+        report.out_of_range[0],
+        IndexError::OutOfRange {
+            face: 9,
+            index: 99,
+            ..
+        }
+    ));
+    assert!(mesh.validate().is_err());
+}
+
+#[test]
+pub fn compute_normals() {
+    let mut mesh = IndexMesh::from_trimesh(&cube(Vec3::ZERO, 2.0), 0.0);
+    mesh.compute_normals(&NormalOptions::default());
+    assert_eq!(mesh.normals.len(), 8);
+    for face in &mesh.faces {
+        for corner in face.corners {
+            let expected = mesh.vertices[corner.position].normalize();
+            assert!(mesh.normals[corner.normal].abs_diff_eq(expected, 1e-6));
+        }
+    }
+
+    // the cube's diagonals make area weighting lean towards split sides
+    mesh.compute_normals(&NormalOptions {
+        weighting: NormalWeighting::Area,
+        ..Default::default()
+    });
+    assert!(mesh
+        .normals
+        .iter()
+        .any(|n| !n.abs_diff_eq(n.signum() / 3f32.sqrt(), 1e-3)));
+
+    mesh.compute_normals(&NormalOptions {
+        crease_angle: Some(0.5),
+        ..Default::default()
+    });
+    assert_eq!(mesh.normals.len(), 24);
+    for face in &mesh.faces {
+        let [a, b, c] = face.positions().map(|p| mesh.vertices[p]);
+        let expected = (b - a).cross(c - a).normalize();
+        assert!(face
+            .corners
+            .iter()
+            .all(|c| mesh.normals[c.normal] == expected));
+    }
+
+    // a single hard edge can't split a closed fan, the faces are still connected the other way
+    let [a, b, c] = mesh.faces[0].positions();
+    let hard = |hard_edges| NormalOptions {
+        hard_edges,
+        ..Default::default()
+    };
+    mesh.compute_normals(&hard(vec![[b, a]]));
+    assert_eq!(mesh.normals.len(), 8);
+    mesh.compute_normals(&hard(vec![[b, a], [b, c], [c, a]]));
+    assert_eq!(mesh.normals.len(), 11);
+
+    let mut mesh = IndexMesh::from_trimesh(&sphere(16, 32), 0.0);
+    mesh.compute_normals(&NormalOptions {
+        crease_angle: Some(0.5),
+        ..Default::default()
+    });
+    for face in &mesh.faces {
+        for corner in face.corners {
+            let expected = mesh.vertices[corner.position];
+            assert!(mesh.normals[corner.normal].dot(expected) > 0.99);
+        }
+    }
+}
+
+#[test]
+pub fn compute_tangents() {
+    let tangents = |mesh: &IndexMesh| {
+        mesh.faces
+            .iter()
+            .flat_map(|f| f.corners)
+            .map(|c| (mesh.tangents[c.tangent.unwrap()], mesh.normals[c.normal]))
+            .collect::<Vec<_>>()
+    };
+
+    let mut mesh = seam_grid(4);
+    mesh.compute_tangents().unwrap();
+    for (t, _) in tangents(&mesh) {
+        assert!(t.abs_diff_eq(Vec4::new(1.0, 0.0, 0.0, 1.0), 1e-6));
+    }
+
+    // mirrored uvs flip the tangent, the bitangent still follows v
+    for uv in &mut mesh.uvs {
+        uv.x = -uv.x;
+    }
+    mesh.compute_tangents().unwrap();
+    for (t, n) in tangents(&mesh) {
+        assert!(t.abs_diff_eq(Vec4::new(-1.0, 0.0, 0.0, -1.0), 1e-6));
+        assert!((t.w * n.cross(t.truncate())).abs_diff_eq(Vec3::Y, 1e-6));
+    }
+
+    for uv in &mut mesh.uvs {
+        uv.x = -uv.x;
+    }
+    for v in &mut mesh.vertices {
+        v.z = v.x * v.x;
+    }
+    mesh.compute_normals(&NormalOptions::default());
+    mesh.compute_tangents().unwrap();
+    for (t, n) in tangents(&mesh) {
+        assert!(t.truncate().dot(n).abs() < 1e-5);
+        assert!((t.truncate().length() - 1.0).abs() < 1e-5);
+        assert!(t.x > 0.0 && t.y.abs() < 1e-5 && t.w == 1.0);
+    }
+
+    let buffer = mesh.vertex_buffer::<u16>().unwrap();
+    assert_eq!(buffer.tangents.len(), buffer.vertices.len());
+
+    // without uvs there's no direction to follow
+    let mut mesh = two_quads(Vec3::ZERO);
+    mesh.compute_tangents().unwrap();
+    assert_eq!(mesh.tangents, [Vec4::new(1.0, 0.0, 0.0, -1.0)]);
+}
+
+/// a mesh with a position, normal and uv of its own for every corner
+fn corner_mesh(triangles: &[[(Vec3, Vec3, Vec2); 3]]) -> IndexMesh {
+    let mut mesh = IndexMesh::default();
+    for t in triangles {
+        let c = t
+            .map(|(p, n, uv)| Corner::new(mesh.add_vertex(p), mesh.add_uv(uv), mesh.add_normal(n)));
+        mesh.add_triangle(c[0], c[1], c[2]);
+    }
+
+    mesh
+}
+
+#[test]
+pub fn compute_tangents_reference() {
+    let check = |triangles: &[[(Vec3, Vec3, Vec2); 3]], expected: &[Vec4]| {
+        let mut mesh = corner_mesh(triangles);
+        mesh.compute_tangents().unwrap();
+        let corners = mesh.faces.iter().flat_map(|f| f.corners);
+        for (c, e) in corners.zip(expected) {
+            assert!(mesh.tangents[c.tangent.unwrap()].abs_diff_eq(*e, 1e-5));
+        }
+    };
+
+    // two quads with mirrored uvs on an uneven surface
+    let heights = [0.0, 0.2, 0.1, 0.3, 0.0, 0.25];
+    let corner = |x: usize, y: usize| {
+        let p = Vec3::new(x as f32, y as f32, heights[y * 3 + x]);
+        let n = Vec3::new(x as f32 * 0.1 - 0.1, y as f32 * 0.05, 1.0).normalize();
+        (p, n, Vec2::new((x as f32 - 1.0).abs(), y as f32))
+    };
+    let mut triangles = Vec::new();
+    for x in 0..2 {
+        triangles.push([corner(x, 0), corner(x + 1, 0), corner(x + 1, 1)]);
+        triangles.push([corner(x, 0), corner(x + 1, 1), corner(x, 1)]);
+    }
+
+    // generated by the reference implementation
+    check(
+        &triangles,
+        &[
+            Vec4::new(-0.995037, 0.000000, -0.099504, -1.0),
+            Vec4::new(-1.000000, 0.000000, 0.000000, -1.0),
+            Vec4::new(-0.999997, -0.002363, 0.000118, -1.0),
+            Vec4::new(-0.995037, 0.000000, -0.099504, -1.0),
+            Vec4::new(-0.999997, -0.002363, 0.000118, -1.0),
+            Vec4::new(-0.994930, -0.020461, -0.098470, -1.0),
+            Vec4::new(1.000000, 0.000000, 0.000000, 1.0),
+            Vec4::new(0.995037, 0.000000, -0.099504, 1.0),
+            Vec4::new(0.995042, -0.008929, -0.099058, 1.0),
+            Vec4::new(1.000000, 0.000000, 0.000000, 1.0),
+            Vec4::new(0.995042, -0.008929, -0.099058, 1.0),
+            Vec4::new(0.999922, -0.012468, 0.000623, 1.0),
+        ],
+    );
+
+    // faces without a uv direction and degenerate faces don't add to their neighbours
+    let c = |p: Vec3, uv: Vec2| (p, Vec3::Z, uv);
+    let first = [
+        c(Vec3::ZERO, Vec2::ZERO),
+        c(Vec3::X, Vec2::X),
+        c(Vec3::Y, Vec2::Y),
+    ];
+    let collinear = [
+        c(Vec3::X, Vec2::X),
+        c(Vec3::new(1.0, 1.0, 0.0), Vec2::splat(0.5)),
+        c(Vec3::Y, Vec2::Y),
+    ];
+    let degenerate = [
+        c(Vec3::X, Vec2::X),
+        c(Vec3::X, Vec2::new(0.7, 0.7)),
+        c(Vec3::Y, Vec2::Y),
+    ];
+    let last = [
+        c(Vec3::X, Vec2::X),
+        c(Vec3::new(1.0, 1.0, 0.0), Vec2::ONE),
+        c(Vec3::Y, Vec2::Y),
+    ];
+
+    let mut expected = [Vec4::new(1.0, 0.0, 0.0, 1.0); 9];
+    expected[4].w = -1.0;
+    check(&[first, collinear], &expected[..6]);
+    check(&[first, degenerate, last], &expected);
+}
+
+#[test]
+pub fn split_by_material() {
+    let mut mesh = two_quads(Vec3::ZERO);
+    mesh.faces[1].material = 3;
+    mesh.faces[2].material = 3;
+    mesh.compute_tangents().unwrap();
+
+    let parts = mesh.clone().split_by_material();
+    assert_eq!(parts.iter().map(|(m, _)| *m).collect::<Vec<_>>(), [0, 3]);
+    for (material, part) in &parts {
+        assert_eq!(part.facecount(), 2);
+        assert_eq!(part.vertices.len(), 6);
+        assert!(part.faces.iter().all(|f| f.material == *material));
+        assert!(part.check_indices().is_ok());
+    }
+
+    let merged = IndexMesh::merge(parts.into_iter().map(|(_, p)| p));
+    assert!(merged.check_indices().is_ok());
+    assert_eq!(merged.vertices.len(), 12);
+    let triangles = |m: &IndexMesh| {
+        let mut t: Vec<_> = m
+            .triangles()
+            .map(|t| {
+                let t = t.unwrap();
+                (t.material, t.points.map(|p| p.to_array().map(f32::to_bits)))
+            })
+            .collect();
+        t.sort_unstable();
+        t
+    };
+    assert_eq!(triangles(&merged), triangles(&mesh));
+}
+
+#[test]
+pub fn repair_out_of_range() {
+    let mut mesh = IndexMesh::from_trimesh(&cube(Vec3::ZERO, 2.0), 0.0);
+    let face = mesh.faces[0];
+    mesh.faces.push(face);
+    mesh.faces[12].corners[1].position = 100;
+
+    let (repaired, log) = mesh.repair(RepairOptions::default());
+    assert_eq!(log, [RepairChange::RemovedOutOfRange { faces: vec![12] }]);
+    assert!(repaired.analyze().is_watertight());
+}
+
+#[test]
+pub fn hole_loops_shared_vertex() {
+    let cube = IndexMesh::from_trimesh(&cube(Vec3::ZERO, 2.0), 0.0);
+    let mut pairs = 0;
+    for f in 0..12 {
+        for g in f + 1..12 {
+            let (a, b) = (cube.faces[f].positions(), cube.faces[g].positions());
+            if a.iter().filter(|v| b.contains(v)).count() != 1 {
+                continue;
+            }
+
+            // two triangular holes touching at a corner
+            let mut mesh = cube.clone();
+            mesh.faces.remove(g);
+            mesh.faces.remove(f);
+            let loops = mesh.hole_loops();
+            assert_eq!(loops.len(), 2);
+            assert!(loops.iter().all(|l| l.len() == 3));
+
+            assert_eq!(mesh.fill_holes(FillOptions::default()), 2);
+            assert!(mesh.analyze().is_watertight());
+            pairs += 1;
+        }
+    }
+
+    assert!(pairs > 0);
+}
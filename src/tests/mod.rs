@@ -1,2 +1,12 @@
+mod bvh;
+mod csg;
+mod halfedge;
+mod hull;
+mod index;
 mod plane;
+mod polygon;
+mod polymesh;
+mod shapes;
 mod triangle;
+mod triangulate;
+mod trimesh;
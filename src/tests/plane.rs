@@ -2,7 +2,7 @@ use glam::Vec3;
 
 use crate::{
     plane::{Plane, Side},
-    traits::Intersect,
+    traits::{Intersect, Raycast},
     Ray, Segment,
 };
 
@@ -66,3 +66,15 @@ pub fn intersect_segment_away() {
 
     assert_eq!(plane.intersects(&segment), None);
 }
+
+#[test]
+pub fn raycast() {
+    let plane = Plane::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+    let ray: Ray = (Vec3::new(0.0, -1.0, 0.1), Vec3::new(0.0, 2.0, 0.0));
+
+    let hit = plane.raycast(&ray).unwrap();
+    assert_eq!(hit.point, Vec3::new(0.0, 0.0, 0.1));
+    assert_eq!(hit.distance, 0.5);
+    assert_eq!(hit.normal, Vec3::Y);
+    assert!(!hit.front_face);
+}
@@ -0,0 +1,23 @@
+use glam::{Vec2, Vec3};
+
+use crate::{polygon::Polygon, traits::Raycast, vertex::Vertex, Ray};
+
+#[test]
+pub fn raycast() {
+    let polygon = Polygon::new(vec![
+        Vertex::new(Vec3::new(0.0, 0.0, 0.0), Vec2::new(0.0, 0.0), Vec3::Y),
+        Vertex::new(Vec3::new(0.0, 0.0, 1.0), Vec2::new(0.0, 1.0), Vec3::Y),
+        Vertex::new(Vec3::new(1.0, 0.0, 1.0), Vec2::new(1.0, 1.0), Vec3::Y),
+        Vertex::new(Vec3::new(1.0, 0.0, 0.0), Vec2::new(1.0, 0.0), Vec3::Y),
+    ]);
+    let ray: Ray = (Vec3::new(0.75, 1.0, 0.25), Vec3::NEG_Y);
+
+    let hit = polygon.raycast(&ray).unwrap();
+    assert_eq!(hit.point, Vec3::new(0.75, 0.0, 0.25));
+    assert_eq!(hit.uv, Vec2::new(0.75, 0.25));
+    assert_eq!(hit.normal, Vec3::Y);
+    assert!(hit.front_face);
+
+    let miss: Ray = (Vec3::new(1.5, 1.0, 0.25), Vec3::NEG_Y);
+    assert_eq!(polygon.raycast(&miss), None);
+}
@@ -0,0 +1,163 @@
+use glam::{Vec2, Vec3};
+
+use super::shapes::quad_cube;
+use crate::{
+    meshes::{CatmullClarkOptions, PolyMesh, TriMesh},
+    polygon::Polygon,
+    vertex::Vertex,
+};
+
+#[test]
+pub fn catmull_clark() {
+    let mesh = quad_cube(Vec3::ZERO, 2.0);
+    let options = CatmullClarkOptions {
+        levels: 2,
+        ..Default::default()
+    };
+    let smooth = mesh.catmull_clark(&options);
+
+    assert_eq!(smooth.polycount(), 6 * 16);
+    assert!(smooth.polygons.iter().all(|p| p.len() == 4));
+
+    let volume = TriMesh::from(smooth.clone()).volume();
+    assert!(volume > 2.5 && volume < 3.0);
+    for v in smooth.polygons.iter().flat_map(|p| &p.vertices) {
+        assert!(v.point.abs().max_element() < 1.0);
+        // the normals of a cube's limit surface point away from it's center
+        assert!(v.normal.dot(v.point) > 0.0);
+        assert!(v.uv.cmpge(Vec2::ZERO).all() && v.uv.cmple(Vec2::ONE).all());
+    }
+}
+
+#[test]
+pub fn catmull_clark_creases() {
+    let mesh = quad_cube(Vec3::ZERO, 2.0);
+    let mut creases = Vec::new();
+    for p in &mesh.polygons {
+        for i in 0..4 {
+            creases.push(([p[i].point, p[(i + 1) % 4].point], f32::INFINITY));
+        }
+    }
+
+    let options = CatmullClarkOptions { levels: 2, creases };
+    let sharp = mesh.catmull_clark(&options);
+    assert!((TriMesh::from(sharp.clone()).volume() - 8.0).abs() < 1e-4);
+    // the normals don't bend around the sharp edges
+    for p in &sharp.polygons {
+        let normal = (p[1].point - p[0].point).cross(p[2].point - p[0].point);
+        assert!(p
+            .vertices
+            .iter()
+            .all(|v| v.normal.dot(normal.normalize()) > 0.9999));
+    }
+
+    // a single crease pulls the surface towards that edge
+    let edge = [Vec3::new(1.0, 1.0, -1.0), Vec3::new(1.0, 1.0, 1.0)];
+    let options = CatmullClarkOptions {
+        levels: 2,
+        creases: vec![(edge, 2.0)],
+    };
+    let creased = mesh.catmull_clark(&options);
+    let smooth = mesh.catmull_clark(&CatmullClarkOptions {
+        levels: 2,
+        ..Default::default()
+    });
+    let furthest = |mesh: &PolyMesh| {
+        mesh.polygons
+            .iter()
+            .flat_map(|p| &p.vertices)
+            .map(|v| v.point.x + v.point.y)
+            .fold(f32::MIN, f32::max)
+    };
+    assert!(furthest(&creased) > furthest(&smooth) + 0.1);
+}
+
+#[test]
+pub fn catmull_clark_non_manifold() {
+    // three quads sharing the edge along y
+    let quad = |to: Vec3| {
+        Polygon::new(
+            [Vec3::ZERO, to, to + Vec3::Y, Vec3::Y]
+                .map(|p| Vertex::new(p, Vec2::ZERO, Vec3::ZERO))
+                .to_vec(),
+        )
+    };
+    let mesh = PolyMesh::new(vec![quad(Vec3::X), quad(Vec3::Z), quad(Vec3::NEG_X)]);
+    let smooth = mesh.catmull_clark(&CatmullClarkOptions::default());
+
+    for p in &smooth.polygons {
+        let normal = (p[1].point - p[0].point).cross(p[2].point - p[0].point);
+        assert!(p
+            .vertices
+            .iter()
+            .all(|v| v.normal.dot(normal.normalize()) > 0.9999));
+    }
+}
+
+#[test]
+pub fn catmull_clark_boundary() {
+    let uvs = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y];
+    let quad = Polygon::new(
+        (0..4)
+            .map(|i| Vertex::new(uvs[i].extend(0.0), uvs[i], Vec3::Z))
+            .collect(),
+    );
+    let mesh = PolyMesh::new(vec![quad]).catmull_clark(&CatmullClarkOptions::default());
+
+    assert_eq!(mesh.polycount(), 4);
+    for v in mesh.polygons.iter().flat_map(|p| &p.vertices) {
+        // a flat open quad keeps it's shape
+        assert_eq!(v.point, v.uv.extend(0.0));
+        assert_eq!(v.normal, Vec3::Z);
+    }
+}
+
+#[test]
+pub fn catmull_clark_attributes() {
+    let mut mesh = quad_cube(Vec3::ZERO, 2.0);
+    let uvs: Vec<Vec2> = mesh
+        .polygons
+        .iter()
+        .flat_map(|p| p.vertices.iter().map(|v| v.uv))
+        .collect();
+    mesh.attributes.insert("uv", uvs);
+
+    let options = CatmullClarkOptions {
+        levels: 2,
+        ..Default::default()
+    };
+    let smooth = mesh.catmull_clark(&options);
+
+    // attributes follow the uvs, both are linear within the polygons
+    let values = smooth.attributes.get::<Vec2>("uv").unwrap();
+    assert_eq!(values.len(), smooth.vertex_count());
+    let uvs = smooth.polygons.iter().flat_map(|p| &p.vertices);
+    for (value, v) in values.iter().zip(uvs) {
+        assert!(value.abs_diff_eq(v.uv, 1e-6));
+    }
+}
+
+#[test]
+pub fn catmull_clark_materials() {
+    let mut mesh = quad_cube(Vec3::ZERO, 2.0);
+    for (i, p) in mesh.polygons.iter_mut().enumerate() {
+        p.material = i as u32;
+    }
+
+    let smooth = mesh.catmull_clark(&CatmullClarkOptions::default());
+    let parts = smooth.split_by_material();
+    assert_eq!(parts.len(), 6);
+    for (i, (material, part)) in parts.iter().enumerate() {
+        assert_eq!(*material, i as u32);
+        assert_eq!(part.polycount(), 4);
+    }
+
+    // every quad stays on the side of the cube it came from
+    let merged = PolyMesh::merge(parts.into_iter().map(|(_, p)| p));
+    assert_eq!(merged.polycount(), 24);
+    let center = |p: &Polygon| p.vertices.iter().map(|v| v.point).sum::<Vec3>().normalize();
+    for p in &merged.polygons {
+        let side = center(&mesh.polygons[p.material as usize]);
+        assert!(center(p).dot(side) > 0.5);
+    }
+}
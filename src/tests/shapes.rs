@@ -0,0 +1,210 @@
+use glam::{Vec2, Vec3};
+
+use crate::{
+    meshes::{Corner, IndexMesh, PolyMesh, TriMesh},
+    polygon::Polygon,
+    triangle::Triangle,
+    triangulate::triangulate,
+    vertex::Vertex,
+};
+
+/// An axis aligned cube with outward facing triangles
+pub fn cube(center: Vec3, size: f32) -> TriMesh {
+    let mut mesh = TriMesh::default();
+    for [a, b, c, d] in cube_quads(center, size) {
+        mesh.add(Triangle::from_points([a, b, c]));
+        mesh.add(Triangle::from_points([a, c, d]));
+    }
+
+    mesh
+}
+
+/// An axis aligned cube with outward facing quads
+pub fn quad_cube(center: Vec3, size: f32) -> PolyMesh {
+    let polygons = cube_quads(center, size)
+        .into_iter()
+        .map(|quad| {
+            let normal = (quad[1] - quad[0]).cross(quad[2] - quad[0]).normalize();
+            let uvs = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y];
+            Polygon::new(
+                (0..4)
+                    .map(|i| Vertex::new(quad[i], uvs[i], normal))
+                    .collect(),
+            )
+        })
+        .collect();
+
+    PolyMesh::new(polygons)
+}
+
+fn cube_quads(center: Vec3, size: f32) -> [[Vec3; 4]; 6] {
+    let h = size * 0.5;
+    let p = |x: f32, y: f32, z: f32| center + Vec3::new(x, y, z) * h;
+    [
+        [
+            p(1., -1., -1.),
+            p(1., 1., -1.),
+            p(1., 1., 1.),
+            p(1., -1., 1.),
+        ],
+        [
+            p(-1., -1., -1.),
+            p(-1., -1., 1.),
+            p(-1., 1., 1.),
+            p(-1., 1., -1.),
+        ],
+        [
+            p(-1., 1., -1.),
+            p(-1., 1., 1.),
+            p(1., 1., 1.),
+            p(1., 1., -1.),
+        ],
+        [
+            p(-1., -1., -1.),
+            p(1., -1., -1.),
+            p(1., -1., 1.),
+            p(-1., -1., 1.),
+        ],
+        [
+            p(-1., -1., 1.),
+            p(1., -1., 1.),
+            p(1., 1., 1.),
+            p(-1., 1., 1.),
+        ],
+        [
+            p(-1., -1., -1.),
+            p(-1., 1., -1.),
+            p(1., 1., -1.),
+            p(1., -1., -1.),
+        ],
+    ]
+}
+
+/// A closed prism extruding `outline` from z = 0 to z = `height`
+pub fn prism(outline: &[Vec2], height: f32) -> TriMesh {
+    let top = |p: Vec2| p.extend(height);
+    let mut mesh = TriMesh::default();
+    for [a, b, c] in triangulate(outline, &[]) {
+        let [a, b, c] = [outline[a], outline[b], outline[c]];
+        mesh.add(Triangle::from_points([top(a), top(b), top(c)]));
+        mesh.add(Triangle::from_points([
+            c.extend(0.0),
+            b.extend(0.0),
+            a.extend(0.0),
+        ]));
+    }
+
+    for i in 0..outline.len() {
+        let (a, b) = (outline[i], outline[(i + 1) % outline.len()]);
+        mesh.add(Triangle::from_points([
+            a.extend(0.0),
+            b.extend(0.0),
+            top(b),
+        ]));
+        mesh.add(Triangle::from_points([a.extend(0.0), top(b), top(a)]));
+    }
+
+    mesh
+}
+
+/// A unit sphere around the origin made of `rings` rings of `segments` quads
+pub fn sphere(rings: usize, segments: usize) -> TriMesh {
+    let point = |ring: usize, segment: usize| {
+        if ring == 0 || ring == rings {
+            return if ring == 0 { Vec3::Z } else { Vec3::NEG_Z };
+        }
+
+        let theta = std::f32::consts::PI * ring as f32 / rings as f32;
+        let phi = std::f32::consts::TAU * (segment % segments) as f32 / segments as f32;
+        Vec3::new(
+            theta.sin() * phi.cos(),
+            theta.sin() * phi.sin(),
+            theta.cos(),
+        )
+    };
+
+    let mut mesh = TriMesh::default();
+    for ring in 0..rings {
+        for segment in 0..segments {
+            let [a, b, c, d] = [
+                point(ring, segment),
+                point(ring + 1, segment),
+                point(ring + 1, segment + 1),
+                point(ring, segment + 1),
+            ];
+            if ring > 0 {
+                mesh.add(Triangle::from_points([a, b, d]));
+            }
+
+            if ring + 1 < rings {
+                mesh.add(Triangle::from_points([b, c, d]));
+            }
+        }
+    }
+
+    mesh
+}
+
+/// A wavy grid of 2 * size * size triangles
+pub fn wavy_grid(size: usize) -> TriMesh {
+    let point = |x: usize, z: usize| {
+        let (x, z) = (x as f32, z as f32);
+        Vec3::new(x, (x * 0.7).sin() + (z * 0.3).cos(), z)
+    };
+
+    let mut mesh = TriMesh::default();
+    for x in 0..size {
+        for z in 0..size {
+            let [a, b, c, d] = [
+                point(x, z),
+                point(x + 1, z),
+                point(x + 1, z + 1),
+                point(x, z + 1),
+            ];
+            mesh.add(Triangle::from_points([a, c, b]));
+            mesh.add(Triangle::from_points([a, d, c]));
+        }
+    }
+
+    mesh
+}
+
+/// a flat unit square of `n` by `n` quads,
+/// the uvs of the right half are offset to form a seam
+pub fn seam_grid(n: usize) -> IndexMesh {
+    let mut mesh = IndexMesh::default();
+    let normal = mesh.add_normal(Vec3::Z);
+    let offset = |x: usize| {
+        if 2 * x < n {
+            Vec2::ZERO
+        } else {
+            Vec2::X * 10.0
+        }
+    };
+    for y in 0..=n {
+        for x in 0..=n {
+            mesh.vertices
+                .push(Vec3::new(x as f32, y as f32, 0.0) / n as f32);
+        }
+    }
+
+    for y in 0..n {
+        for x in 0..n {
+            let c = [(0, 0), (1, 0), (1, 1), (0, 1)].map(|(dx, dy)| {
+                let v = (y + dy) * (n + 1) + x + dx;
+                let uv = mesh.vertices[v].truncate() + offset(x);
+                Corner::new(v, mesh.add_uv(uv), normal)
+            });
+            mesh.add_triangle(c[0], c[1], c[2]);
+            mesh.add_triangle(c[0], c[2], c[3]);
+        }
+    }
+
+    mesh
+}
+
+/// A deterministic pseudo random number between 0 and 1
+pub fn random(seed: &mut u32) -> f32 {
+    *seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+    (*seed >> 8) as f32 / (1 << 24) as f32
+}
@@ -1,6 +1,11 @@
 use glam::{Vec2, Vec3};
 
-use crate::{plane::Plane, traits::Intersect, triangle::Triangle, Ray, Segment};
+use crate::{
+    plane::Plane,
+    traits::{Intersect, Raycast},
+    triangle::Triangle,
+    Ray, Segment,
+};
 
 // ---- Intersection ----
 #[test]
@@ -256,3 +261,42 @@ pub fn slice() {
         )
     );
 }
+
+#[test]
+pub fn raycast() {
+    let triangle = Triangle::new(
+        [
+            Vec3::new(0.0, 0.0, 0.5),
+            Vec3::new(0.5, 0.0, -0.5),
+            Vec3::new(-0.5, 0.0, -0.5),
+        ],
+        [
+            Vec2::new(0.0, 1.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 0.0),
+        ],
+        Vec3::Y,
+    );
+    let ray: Ray = (Vec3::new(0.0, 2.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+
+    let hit = triangle.raycast(&ray).unwrap();
+    assert_eq!(hit.point, Vec3::ZERO);
+    assert_eq!(hit.distance, 2.0);
+    assert_eq!(hit.barycentric, Vec3::new(0.5, 0.25, 0.25));
+    assert_eq!(hit.uv, Vec2::new(0.25, 0.5));
+    assert!(hit.front_face);
+
+    let below: Ray = (Vec3::new(0.0, -2.0, 0.0), Vec3::Y);
+    assert!(!triangle.raycast(&below).unwrap().front_face);
+    assert_eq!(triangle.raycast_range(&ray, 0.0, 1.5), None);
+    assert_eq!(triangle.raycast_range(&ray, 2.5, 3.0), None);
+}
+
+#[test]
+pub fn area() {
+    let triangle = Triangle::from_points([Vec3::ZERO, Vec3::X * 2.0, Vec3::new(1.0, 3.0, 0.0)]);
+    assert_eq!(triangle.area(), 3.0);
+
+    let flat = Triangle::from_points([Vec3::ZERO, Vec3::X, Vec3::X * 2.0]);
+    assert_eq!(flat.area(), 0.0);
+}
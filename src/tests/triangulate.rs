@@ -0,0 +1,29 @@
+use glam::Vec2;
+
+use crate::triangulate::{signed_area, triangulate};
+
+#[test]
+pub fn triangulate_hole() {
+    let outer = [
+        Vec2::new(-1.0, -1.0),
+        Vec2::new(1.0, -1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(-1.0, 1.0),
+    ];
+    let hole = vec![
+        Vec2::new(-0.5, -0.5),
+        Vec2::new(-0.5, 0.5),
+        Vec2::new(0.5, 0.5),
+        Vec2::new(0.5, -0.5),
+    ];
+
+    let points: Vec<Vec2> = outer.iter().chain(&hole).copied().collect();
+    let triangles = triangulate(&outer, &[hole]);
+    let area: f32 = triangles
+        .iter()
+        .map(|t| signed_area(&t.map(|i| points[i])))
+        .sum();
+
+    assert_eq!(triangles.len(), 8);
+    assert_eq!(area, 3.0);
+}
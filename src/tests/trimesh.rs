@@ -0,0 +1,648 @@
+use glam::{Vec2, Vec3};
+
+use super::shapes::{cube, sphere};
+use crate::{
+    meshes::{
+        Connectivity, FillMethod, FillOptions, HalfEdgeMesh, IndexMesh, LayerSpacing, LoopOptions,
+        OrientMethod, Orientation, PolyMesh, RepairChange, RepairOptions, SeparateOptions,
+        SimplifyOptions, TriMesh,
+    },
+    plane::Plane,
+    triangle::Triangle,
+};
+
+#[test]
+pub fn separate_by_loose_parts() {
+    let mesh = TriMesh::new(vec![
+        Triangle::from_points([Vec3::ZERO, Vec3::X, Vec3::Y]),
+        Triangle::from_points([Vec3::Z, Vec3::Z + Vec3::X, Vec3::Z + Vec3::Y]),
+        Triangle::from_points([Vec3::X, Vec3::new(1.0, 1.0, 0.0), Vec3::Y]),
+    ]);
+
+    let parts = mesh.separate_by_loose_parts();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].tricount(), 2);
+    assert_eq!(parts[1].tricount(), 1);
+    assert_eq!(parts[1][0][0], Vec3::Z);
+}
+
+#[test]
+pub fn separate_by_loose_parts_edge() {
+    // two triangles touching at a single vertex
+    let mesh = TriMesh::new(vec![
+        Triangle::from_points([Vec3::ZERO, Vec3::X, Vec3::Y]),
+        Triangle::from_points([Vec3::ZERO, -Vec3::X, -Vec3::Y]),
+    ]);
+
+    let parts = mesh.clone().separate_by_loose_parts();
+    assert_eq!(parts.len(), 1);
+
+    let parts = mesh.separate_by_loose_parts_with(SeparateOptions {
+        connectivity: Connectivity::Edge,
+        ..Default::default()
+    });
+    assert_eq!(parts.len(), 2);
+}
+
+#[test]
+pub fn separate_by_loose_parts_tolerance() {
+    let mesh = TriMesh::new(vec![
+        Triangle::from_points([Vec3::ZERO, Vec3::X, Vec3::Y]),
+        Triangle::from_points([
+            Vec3::new(1.0001, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::Y * 2.0,
+        ]),
+    ]);
+
+    assert_eq!(mesh.clone().separate_by_loose_parts().len(), 2);
+
+    let parts = mesh.separate_by_loose_parts_with(SeparateOptions {
+        tolerance: Some(0.001),
+        ..Default::default()
+    });
+    assert_eq!(parts.len(), 1);
+}
+
+#[test]
+pub fn separate_by_loose_parts_tolerance_chain() {
+    // only neighbouring corners are within the tolerance
+    let p = |x| Vec3::new(x, 0.0, 0.0);
+    let mesh = TriMesh::new(vec![
+        Triangle::from_points([p(0.0), p(0.0) - Vec3::X, p(0.0) - Vec3::X + Vec3::Y]),
+        Triangle::from_points([
+            p(0.06),
+            p(0.06) + Vec3::Y * 2.0,
+            p(0.06) + Vec3::Y * 2.0 + Vec3::Z,
+        ]),
+        Triangle::from_points([p(0.12), p(0.12) + Vec3::X, p(0.12) + Vec3::X + Vec3::Y]),
+    ]);
+    let options = SeparateOptions {
+        tolerance: Some(0.1),
+        ..Default::default()
+    };
+
+    let index = IndexMesh::from_trimesh(&mesh, 0.0);
+    assert_eq!(mesh.separate_by_loose_parts_with(options).len(), 1);
+    assert_eq!(
+        index.separate_by_loose_parts_with(options).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+pub fn slice_caps() {
+    let mesh = cube(Vec3::ZERO, 2.0);
+    let (above, below) = mesh.slice(&Plane::new(Vec3::new(0.0, 0.0, 0.5), Vec3::Z));
+
+    for (half, normal) in [(&above, Vec3::NEG_Z), (&below, Vec3::Z)] {
+        let caps: Vec<&Triangle> = half
+            .triangles
+            .iter()
+            .filter(|t| t.points.iter().all(|p| p.z == 0.5))
+            .collect();
+
+        let area: f32 = caps.iter().map(|t| t.area()).sum();
+        assert!((area - 4.0).abs() < 1e-5);
+        for t in caps {
+            assert_eq!(t.normal, normal);
+            let winding = (t[1] - t[0]).cross(t[2] - t[0]);
+            assert!(winding.dot(normal) > 0.0);
+        }
+    }
+}
+
+#[test]
+pub fn slice_watertight() {
+    let mesh = cube(Vec3::ZERO, 2.0);
+    for normal in [Vec3::new(1.0, 2.0, 3.0), Vec3::new(-3.0, 1.0, 0.5)] {
+        let plane = Plane::new(Vec3::splat(0.1), normal.normalize());
+        let (above, below) = mesh.slice(&plane);
+        assert!(above.analyze().is_watertight());
+        assert!(below.analyze().is_watertight());
+    }
+}
+
+/// A thick ring made from two nested square tubes along z
+fn ring() -> TriMesh {
+    let mut mesh = TriMesh::default();
+    for (size, flip) in [(2.0, false), (1.0, true)] {
+        for t in cube(Vec3::ZERO, size).triangles {
+            // drop the top and bottom of the tubes
+            if t.points.iter().all(|p| p.z == t[0].z) {
+                continue;
+            }
+
+            mesh.add(if flip { t.flip() } else { t });
+        }
+    }
+
+    mesh
+}
+
+#[test]
+pub fn slice_caps_hole() {
+    let mesh = ring();
+    let (_, below) = mesh.slice(&Plane::new(Vec3::ZERO, Vec3::Z));
+    let area: f32 = below
+        .triangles
+        .iter()
+        .filter(|t| t.points.iter().all(|p| p.z == 0.0))
+        .map(Triangle::area)
+        .sum();
+
+    assert!((area - 3.0).abs() < 1e-5);
+}
+
+#[test]
+pub fn section() {
+    let contours = ring().section(&Plane::new(Vec3::ZERO, Vec3::Z));
+
+    assert_eq!(contours.len(), 2);
+    let outer = &contours[0];
+    let hole = &contours[1];
+    assert!(outer.closed && hole.closed);
+    assert_eq!(outer.orientation, Some(Orientation::Outer));
+    assert_eq!(hole.orientation, Some(Orientation::Hole));
+    assert!((outer.area() - 4.0).abs() < 1e-5);
+    assert!((hole.area() + 1.0).abs() < 1e-5);
+    assert!((outer.length() - 8.0).abs() < 1e-5);
+    assert_eq!(outer.points.len(), outer.points_2d.len());
+}
+
+#[test]
+pub fn section_open() {
+    // an open strip of two triangles crossing the plane
+    let mesh = TriMesh::new(vec![
+        Triangle::from_points([
+            Vec3::new(0.0, -1.0, 0.0),
+            Vec3::new(1.0, -1.0, 0.0),
+            Vec3::new(1.0, 1.0, 0.0),
+        ]),
+        Triangle::from_points([
+            Vec3::new(0.0, -1.0, 0.0),
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        ]),
+    ]);
+
+    let contours = mesh.section(&Plane::new(Vec3::ZERO, Vec3::Y));
+    assert_eq!(contours.len(), 1);
+    assert!(!contours[0].closed);
+    assert_eq!(contours[0].orientation, None);
+    assert_eq!(contours[0].points.len(), 3);
+    assert!((contours[0].length() - 1.0).abs() < 1e-5);
+}
+
+#[test]
+pub fn layers() {
+    let mesh = cube(Vec3::ZERO, 2.0);
+    let layers = mesh.layers(Vec3::Z, LayerSpacing::Count(4));
+
+    let heights: Vec<f32> = layers.iter().map(|l| l.height).collect();
+    assert_eq!(heights, [-0.75, -0.25, 0.25, 0.75]);
+    for layer in &layers {
+        assert_eq!(layer.contours.len(), 1);
+        assert!((layer.area() - 4.0).abs() < 1e-5);
+        assert_eq!(
+            layer.bounds(),
+            Some([
+                Vec3::new(-1.0, -1.0, layer.height),
+                Vec3::new(1.0, 1.0, layer.height)
+            ])
+        );
+    }
+
+    let volume: f32 = layers.iter().map(|l| l.volume()).sum();
+    assert!((volume - 8.0).abs() < 1e-4);
+}
+
+#[test]
+pub fn layers_adaptive() {
+    // an octahedron, every face is sloped at the same angle
+    let points = [Vec3::X, Vec3::Y, Vec3::NEG_X, Vec3::NEG_Y];
+    let mut mesh = TriMesh::default();
+    for i in 0..4 {
+        let (a, b) = (points[i], points[(i + 1) % 4]);
+        mesh.add(Triangle::from_points([a, b, Vec3::Z]));
+        mesh.add(Triangle::from_points([b, a, Vec3::NEG_Z]));
+    }
+
+    let spacing = LayerSpacing::Adaptive {
+        min: 0.01,
+        max: 0.5,
+        tolerance: 0.1 / 3f32.sqrt(),
+    };
+    let layers = mesh.layers(Vec3::Z, spacing);
+
+    assert_eq!(layers.len(), 20);
+    assert!((layers[0].thickness - 0.1).abs() < 1e-5);
+
+    // vertical walls never step
+    assert_eq!(ring().layers(Vec3::Z, spacing).len(), 4);
+}
+
+#[test]
+pub fn layers_invalid() {
+    let mesh = cube(Vec3::ZERO, 2.0);
+    let adaptive = |min, max| LayerSpacing::Adaptive {
+        min,
+        max,
+        tolerance: 0.1,
+    };
+    for spacing in [
+        LayerSpacing::Thickness(0.0),
+        LayerSpacing::Thickness(f32::NAN),
+        adaptive(0.5, 0.1),
+        adaptive(f32::NAN, 0.5),
+        adaptive(0.1, f32::INFINITY),
+    ] {
+        assert!(mesh.layers(Vec3::Z, spacing).is_empty());
+    }
+
+    // too thin to move past the bottom of the mesh
+    let far = cube(Vec3::Z * 1e4, 2.0);
+    assert!(far
+        .layers(Vec3::Z, LayerSpacing::Thickness(1e-6))
+        .is_empty());
+    assert!(far.layers(Vec3::Z, adaptive(1e-6, 1e-6)).is_empty());
+}
+
+#[test]
+pub fn simplify() {
+    let mesh = cube(Vec3::ZERO, 2.0).subdivide().subdivide();
+    let options = SimplifyOptions {
+        max_error: 1e-4,
+        ..Default::default()
+    };
+    let simple = mesh.simplify(options);
+
+    assert!(simple.tricount() < mesh.tricount() / 2);
+    assert!((simple.volume() - 8.0).abs() < 1e-4);
+    for t in &simple.triangles {
+        assert!((t.normal.length() - 1.0).abs() < 1e-5);
+    }
+}
+
+#[test]
+pub fn loop_subdivide() {
+    let mesh = cube(Vec3::ZERO, 2.0);
+    let smooth = mesh.loop_subdivide(&LoopOptions::default());
+
+    assert_eq!(smooth.tricount(), mesh.tricount() * 4);
+    assert!(smooth.volume() > 2.0 && smooth.volume() < 8.0);
+    for t in &smooth.triangles {
+        assert!(t.points.iter().all(|p| p.abs().max_element() <= 1.0));
+    }
+
+    let options = LoopOptions {
+        crease_angle: Some(0.5),
+        ..Default::default()
+    };
+    let sharp = mesh.loop_subdivide(&options).loop_subdivide(&options);
+    assert_eq!(sharp.tricount(), mesh.tricount() * 16);
+    assert!((sharp.volume() - 8.0).abs() < 1e-4);
+}
+
+#[test]
+pub fn analyze() {
+    let mut mesh = cube(Vec3::ZERO, 2.0);
+    let report = mesh.analyze();
+    assert!(report.is_watertight());
+    assert_eq!(report.to_string(), "no problems");
+    assert!(mesh.validate().is_ok());
+
+    mesh.triangles[0] = mesh.triangles[0].flip();
+    mesh.add(mesh.triangles[3]);
+    mesh.add(Triangle::from_points([Vec3::ZERO, Vec3::X, Vec3::X * 2.0]));
+    let report = mesh.analyze();
+    assert_eq!(report.inconsistent_winding.len(), 3);
+    assert_eq!(report.duplicate_faces, vec![[12, 3]]);
+    assert_eq!(report.degenerate_faces, vec![13]);
+    assert!(report.boundary_edges.is_empty());
+    assert_eq!(
+        mesh.validate().unwrap_err().to_string(),
+        "1 degenerate faces, 1 duplicate faces, 3 edges with inconsistent winding"
+    );
+
+    // two cubes touching at a corner
+    let mut mesh = cube(Vec3::ZERO, 2.0);
+    mesh.triangles.extend(cube(Vec3::splat(2.0), 2.0).triangles);
+    mesh.remove(0);
+    let report = mesh.analyze();
+    assert_eq!(report.non_manifold_vertices.len(), 1);
+    assert_eq!(report.boundary_edges.len(), 3);
+    assert!(!report.is_valid());
+
+    assert!(TriMesh::default().min_max().is_none());
+    assert!((cube(Vec3::ZERO, 2.0).surface_area() - 24.0).abs() < 1e-5);
+}
+
+#[test]
+pub fn repair() {
+    let mut mesh = cube(Vec3::ZERO, 2.0);
+    mesh.triangles[0] = mesh.triangles[0].flip();
+    mesh.add(mesh.triangles[3]);
+    mesh.add(Triangle::from_points([Vec3::ZERO, Vec3::X, Vec3::X * 2.0]));
+    mesh.remove(5);
+
+    let (repaired, log) = mesh.repair(RepairOptions::default());
+    assert!(repaired.analyze().is_watertight());
+    assert_eq!(repaired.tricount(), 12);
+    assert!((repaired.volume() - 8.0).abs() < 1e-5);
+    for t in &repaired.triangles {
+        let winding = (t[1] - t[0]).cross(t[2] - t[0]);
+        assert!(winding.dot(t.normal) > 0.0);
+    }
+
+    assert!(matches!(log[0], RepairChange::RemovedDegenerate { ref faces } if faces == &[12]));
+    assert!(matches!(log[1], RepairChange::RemovedDuplicates { ref faces } if faces == &[11]));
+    assert!(matches!(log[2], RepairChange::FlippedFaces { ref faces } if faces == &[0]));
+    assert!(matches!(log[3], RepairChange::FilledHole { faces: 1, .. }));
+    assert_eq!(log.len(), 4);
+
+    // an inside out cube touching another at a corner
+    let mut mesh = TriMesh::new(
+        cube(Vec3::ZERO, 2.0)
+            .triangles
+            .iter()
+            .map(|t| t.flip())
+            .collect(),
+    );
+    mesh.triangles.extend(cube(Vec3::splat(2.0), 2.0).triangles);
+    let (repaired, log) = IndexMesh::from_trimesh(&mesh, 0.0).repair(RepairOptions::default());
+
+    assert!(repaired.analyze().is_watertight());
+    assert!((TriMesh::try_from(repaired).unwrap().volume() - 16.0).abs() < 1e-4);
+    assert!(matches!(log[0], RepairChange::SplitVertex { ref copies, .. } if copies.len() == 1));
+    assert!(matches!(log[1], RepairChange::FlippedFaces { ref faces } if faces.len() == 12));
+}
+
+#[test]
+pub fn orient() {
+    let mut mesh = cube(Vec3::ZERO, 2.0);
+    for t in [0, 5, 7] {
+        mesh.triangles[t] = mesh.triangles[t].flip();
+    }
+
+    let mut inside_out = TriMesh::new(mesh.triangles.iter().map(|t| t.flip()).collect());
+    assert_eq!(mesh.orient(OrientMethod::SignedVolume), [0, 5, 7]);
+    assert!((mesh.volume() - 8.0).abs() < 1e-5);
+    assert_eq!(inside_out.orient(OrientMethod::RayParity).len(), 9);
+    assert!((inside_out.volume() - 8.0).abs() < 1e-5);
+    assert!(mesh.orient(OrientMethod::RayParity).is_empty());
+
+    // a hollow shell, the inner wall facing into the hollow
+    let mut shell = cube(Vec3::ZERO, 4.0);
+    let inner: Vec<Triangle> = cube(Vec3::ZERO, 2.0)
+        .triangles
+        .iter()
+        .map(|t| t.flip())
+        .collect();
+    shell.triangles.extend(&inner);
+    assert_eq!(
+        shell.clone().orient(OrientMethod::RayParity),
+        Vec::<usize>::new()
+    );
+    assert_eq!(
+        shell.orient(OrientMethod::SignedVolume),
+        (12..24).collect::<Vec<_>>()
+    );
+
+    // open parts only get a consistent winding
+    let mut open = cube(Vec3::ZERO, 2.0);
+    open.remove(11);
+    let open = TriMesh::new(open.triangles.iter().map(|t| t.flip()).collect());
+    let mut flipped = open.clone();
+    flipped.triangles[10] = flipped.triangles[10].flip();
+    assert_eq!(flipped.orient(OrientMethod::SignedVolume), [10]);
+    for (a, b) in flipped.triangles.iter().zip(&open.triangles) {
+        assert_eq!((a.points, a.normal), (b.points, b.normal));
+    }
+}
+
+#[test]
+pub fn fill_holes() {
+    let mut open = cube(Vec3::ZERO, 2.0);
+    open.remove(0);
+    open.remove(0);
+
+    for method in [FillMethod::MinimumArea, FillMethod::EarClipping] {
+        let mut mesh = open.clone();
+        let options = FillOptions {
+            method,
+            ..Default::default()
+        };
+        assert_eq!(mesh.fill_holes(options), 1);
+        assert!(mesh.analyze().is_watertight());
+        assert!((mesh.volume() - 8.0).abs() < 1e-5);
+    }
+
+    let mut mesh = open.clone();
+    let small = FillOptions {
+        max_edges: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(mesh.fill_holes(small), 0);
+    let short = FillOptions {
+        max_perimeter: Some(7.9),
+        ..Default::default()
+    };
+    assert_eq!(mesh.fill_holes(short), 0);
+    assert_eq!(mesh.tricount(), 10);
+}
+
+#[test]
+pub fn fill_holes_fair() {
+    let mut open = sphere(12, 16);
+    open.triangles
+        .retain(|t| t.points.iter().any(|p| p.z < 0.8));
+    let kept = open.tricount();
+
+    // returns how far the new vertices are from the sphere on average
+    let fill = |fair: bool| {
+        let mut mesh = open.clone();
+        let options = FillOptions {
+            refine: true,
+            fair,
+            ..Default::default()
+        };
+        assert_eq!(mesh.fill_holes(options), 1);
+        assert!(mesh.analyze().is_watertight());
+
+        let points: Vec<Vec3> = mesh.triangles[kept..]
+            .iter()
+            .flat_map(|t| t.points)
+            .filter(|p| (p.z - 0.866).abs() > 1e-3 || p.truncate().length() < 0.49)
+            .collect();
+        assert!(!points.is_empty());
+        points.iter().map(|p| (1.0 - p.length()).abs()).sum::<f32>() / points.len() as f32
+    };
+
+    let flat = fill(false);
+    let fair = fill(true);
+    assert!(fair < flat * 0.5);
+}
+
+/// gives every corner of `mesh` a "point" attribute equal to its position
+fn with_points(mut mesh: TriMesh) -> TriMesh {
+    let points: Vec<Vec3> = mesh.triangles.iter().flat_map(|t| t.points).collect();
+    mesh.attributes.insert("point", points);
+    mesh
+}
+
+/// returns whether the "point" attribute of every corner still equals its position,
+/// or is zero for triangles that got default values
+fn points_follow(mesh: &TriMesh, defaults: bool) -> bool {
+    let values = mesh.attributes.get::<Vec3>("point").unwrap();
+    values.len() == mesh.tricount() * 3
+        && mesh.triangles.iter().enumerate().all(|(i, t)| {
+            let values = &values[3 * i..3 * i + 3];
+            let follows = (0..3).all(|c| values[c].abs_diff_eq(t[c], 1e-5));
+            follows || (defaults && values.iter().all(|v| *v == Vec3::ZERO))
+        })
+}
+
+#[test]
+pub fn attributes() {
+    let mut mesh = with_points(cube(Vec3::ZERO, 2.0));
+    mesh.attributes.insert("weight", vec![1.0f32; 36]);
+    assert_eq!(
+        mesh.attributes.names().collect::<Vec<_>>(),
+        ["point", "weight"]
+    );
+    assert!(mesh.attributes.get::<Vec2>("point").is_none());
+    mesh.attributes.get_mut::<f32>("weight").unwrap()[0] = 0.5;
+    assert!(mesh.attributes.remove("weight"));
+
+    mesh.remove(4);
+    mesh.add(Triangle::from_points([Vec3::ZERO, Vec3::X, Vec3::Y]));
+    assert!(points_follow(&mesh, true));
+    mesh.remove(11);
+
+    assert!(points_follow(&mesh.subdivide(), false));
+
+    let (above, below) = mesh.slice(&Plane::new(Vec3::new(0.0, 0.0, 0.3), Vec3::Z));
+    assert!(points_follow(&above, true) && points_follow(&below, true));
+
+    let mut flipped = mesh.clone();
+    flipped.triangles[3] = flipped.triangles[3].flip();
+    flipped.attributes.swap(9, 11);
+    flipped.orient(OrientMethod::SignedVolume);
+    assert!(points_follow(&flipped, false));
+
+    let index = IndexMesh::from_trimesh(&mesh, 0.0);
+    assert!(points_follow(
+        &TriMesh::try_from(index.clone()).unwrap(),
+        false
+    ));
+    assert!(points_follow(
+        &TriMesh::from(PolyMesh::from(mesh.clone())),
+        false
+    ));
+
+    // midpoints interpolate along edges, only the points move
+    let smooth = TriMesh::try_from(index.loop_subdivide(&LoopOptions::default())).unwrap();
+    assert_eq!(smooth.attributes.len(), smooth.tricount() * 3);
+    let flat = mesh.subdivide();
+    let values = |m: &TriMesh| {
+        let mut v: Vec<[i32; 3]> = m
+            .attributes
+            .get::<Vec3>("point")
+            .unwrap()
+            .iter()
+            .map(|p| (*p * 1e4).round().to_array().map(|x| x as i32))
+            .collect();
+        v.sort_unstable();
+        v.dedup();
+        v
+    };
+    assert_eq!(values(&smooth), values(&flat));
+
+    let mut two = with_points(cube(Vec3::ZERO, 2.0));
+    two.triangles.extend(cube(Vec3::X * 4.0, 2.0).triangles);
+    let points: Vec<Vec3> = two.triangles.iter().flat_map(|t| t.points).collect();
+    two.attributes.insert("point", points);
+    let parts = two.separate_by_loose_parts();
+    assert_eq!(parts.len(), 2);
+    assert!(parts.iter().all(|p| points_follow(p, false)));
+}
+
+#[test]
+pub fn meshes_are_send_sync() {
+    // attribute channels are boxed, so this fails to compile if a channel isn't
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<TriMesh>();
+    assert_send_sync::<PolyMesh>();
+    assert_send_sync::<IndexMesh>();
+}
+
+/// returns the sorted materials of `mesh`
+fn materials(mesh: &TriMesh) -> Vec<u32> {
+    let mut materials: Vec<u32> = mesh.triangles.iter().map(|t| t.material).collect();
+    materials.sort_unstable();
+    materials
+}
+
+#[test]
+pub fn materials_propagate() {
+    let mut mesh = cube(Vec3::ZERO, 2.0);
+    for (i, t) in mesh.triangles.iter_mut().enumerate() {
+        t.material = 1 + i as u32 % 3;
+    }
+
+    let expected: Vec<u32> = materials(&mesh).into_iter().flat_map(|m| [m; 4]).collect();
+    assert_eq!(materials(&mesh.subdivide()), expected);
+
+    // caps are the only triangles without a material
+    let (above, below) = mesh.slice(&Plane::new(Vec3::new(0.0, 0.0, 0.3), Vec3::Z));
+    for t in above.triangles.iter().chain(&below.triangles) {
+        let on_plane = (t.min().z - 0.3).abs() < 1e-5 && (t.max().z - 0.3).abs() < 1e-5;
+        assert_eq!(t.material == 0, on_plane);
+    }
+
+    let index = IndexMesh::from_trimesh(&mesh, 0.0);
+    assert_eq!(
+        materials(&TriMesh::try_from(index.clone()).unwrap()),
+        materials(&mesh)
+    );
+    assert_eq!(
+        materials(&TriMesh::from(PolyMesh::from(mesh.clone()))),
+        materials(&mesh)
+    );
+    let halfedge = HalfEdgeMesh::try_from(index.clone()).unwrap();
+    assert_eq!(materials(&TriMesh::from(halfedge)), materials(&mesh));
+
+    let smooth = TriMesh::try_from(index.loop_subdivide(&LoopOptions::default())).unwrap();
+    assert_eq!(materials(&smooth), expected);
+
+    let parts = mesh.clone().separate_by_loose_parts();
+    assert_eq!(materials(&parts[0]), materials(&mesh));
+}
+
+#[test]
+pub fn split_by_material() {
+    let mut mesh = with_points(cube(Vec3::ZERO, 2.0));
+    for (i, t) in mesh.triangles.iter_mut().enumerate() {
+        t.material = [7, 2, 5][i % 3];
+    }
+
+    let parts = mesh.clone().split_by_material();
+    assert_eq!(parts.iter().map(|(m, _)| *m).collect::<Vec<_>>(), [2, 5, 7]);
+    for (material, part) in &parts {
+        assert_eq!(part.tricount(), 4);
+        assert!(part.triangles.iter().all(|t| t.material == *material));
+        assert!(points_follow(part, false));
+    }
+
+    let mut plain = cube(Vec3::X * 4.0, 2.0);
+    plain.attributes.insert("weight", vec![1.0f32; 36]);
+    let merged = TriMesh::merge(parts.into_iter().map(|(_, p)| p).chain([plain]));
+    assert_eq!(merged.tricount(), 24);
+    assert_eq!(merged.attributes.len(), 72);
+    assert!(points_follow(&merged, true));
+    let weights = merged.attributes.get::<f32>("weight").unwrap();
+    assert!(weights[..36].iter().all(|&w| w == 0.0));
+    assert!(weights[36..].iter().all(|&w| w == 1.0));
+    assert!((merged.volume() - 16.0).abs() < 1e-4);
+}
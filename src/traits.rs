@@ -1,4 +1,17 @@
+use crate::{hit::RayHit, Ray};
+
 /// A trait for intersection checks
 pub trait Intersect<T, R> {
     fn intersects(&self, other: &T) -> R;
 }
+
+/// A trait for ray casts that return a `RayHit`
+pub trait Raycast {
+    /// returns the closest hit with a distance from `t_min` to `t_max`
+    fn raycast_range(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<RayHit>;
+
+    /// returns the closest hit in front of the ray
+    fn raycast(&self, ray: &Ray) -> Option<RayHit> {
+        self.raycast_range(ray, 0.0, f32::INFINITY)
+    }
+}
@@ -1,33 +1,62 @@
-use std::f32::EPSILON;
-
 use glam::Vec3;
 
-use crate::{plane::Plane, traits::Intersect, Ray, Segment};
+use crate::{
+    hit::RayHit,
+    plane::Plane,
+    traits::{Intersect, Raycast},
+    Ray, Segment,
+};
 
 use super::Triangle;
 
-impl Intersect<Ray, Option<Vec3>> for Triangle {
-    /// get the intersection point of a ray
-    /// returns None if there's no intersection
-    fn intersects(&self, ray: &Ray) -> Option<Vec3> {
-        // https://stackoverflow.com/a/42752998
+impl Raycast for Triangle {
+    /// get the hit record of a ray  
+    /// returns None if there's no intersection in range
+    fn raycast_range(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<RayHit> {
+        // https://en.wikipedia.org/wiki/M%C3%B6ller%E2%80%93Trumbore_intersection_algorithm
         let e1 = self[1] - self[0];
         let e2 = self[2] - self[0];
-        let n = e1.cross(e2);
-        let det = -ray.1.dot(n);
-        let invdet = 1.0 / det;
+        let p = ray.1.cross(e2);
+        let det = e1.dot(p);
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
 
+        let invdet = 1.0 / det;
         let ao = ray.0 - self[0];
-        let dao = ao.cross(ray.1);
-        let u = e2.dot(dao) * invdet;
-        let v = -e1.dot(dao) * invdet;
-        let t = ao.dot(n) * invdet;
-
-        if det.abs() >= EPSILON && t >= 0.0 && u >= 0.0 && v >= 0.0 && (u + v) <= 1.0 {
-            Some(ray.0 + t * ray.1)
-        } else {
-            None
+        let u = ao.dot(p) * invdet;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = ao.cross(e1);
+        let v = ray.1.dot(q) * invdet;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
         }
+
+        let t = e2.dot(q) * invdet;
+        if t < t_min || t > t_max {
+            return None;
+        }
+
+        let barycentric = Vec3::new(1.0 - u - v, u, v);
+        Some(RayHit {
+            point: ray.0 + t * ray.1,
+            distance: t,
+            barycentric,
+            front_face: ray.1.dot(self.normal) < 0.0,
+            uv: self.uvs[0] * barycentric.x + self.uvs[1] * u + self.uvs[2] * v,
+            normal: self.normal,
+        })
+    }
+}
+
+impl Intersect<Ray, Option<Vec3>> for Triangle {
+    /// get the intersection point of a ray
+    /// returns None if there's no intersection
+    fn intersects(&self, ray: &Ray) -> Option<Vec3> {
+        self.raycast(ray).map(|hit| hit.point)
     }
 }
 
@@ -37,10 +66,7 @@ impl Intersect<Segment, Option<Vec3>> for Triangle {
     fn intersects(&self, segment: &Segment) -> Option<Vec3> {
         // https://stackoverflow.com/a/58694277
         let plane = Plane::new(self[0], self.normal);
-        let point = match plane.intersects(segment) {
-            Some(point) => point,
-            None => return None,
-        };
+        let point = plane.intersects(segment)?;
 
         let n12 = (self[1] - self[0]).cross(self.normal);
         let n23 = (self[2] - self[1]).cross(self.normal);
@@ -49,7 +75,7 @@ impl Intersect<Segment, Option<Vec3>> for Triangle {
         let da = (point - self[0]).dot(n12) / n12.length();
         let db = (point - self[1]).dot(n23) / n23.length();
         let dc = (point - self[2]).dot(n31) / n31.length();
-        if da < -EPSILON && db < -EPSILON && dc < -EPSILON {
+        if da < -f32::EPSILON && db < -f32::EPSILON && dc < -f32::EPSILON {
             return Some(point);
         }
 
@@ -12,6 +12,8 @@ pub struct Triangle {
     pub points: [Vec3; 3],
     pub uvs: [Vec2; 3],
     pub normal: Vec3,
+    /// the material or group this triangle belongs to
+    pub material: u32,
 }
 
 impl Triangle {
@@ -20,6 +22,7 @@ impl Triangle {
             points,
             uvs,
             normal,
+            material: 0,
         }
     }
 
@@ -49,20 +52,66 @@ impl Triangle {
 
     /// Calculates the area of this triangle
     pub fn area(&self) -> f32 {
-        // https://math.stackexchange.com/a/128995
         let ab = self[1] - self[0];
         let ac = self[2] - self[0];
-        ab.dot(ac) * 0.5
+        ab.cross(ac).length() * 0.5
+    }
+
+    /// returns the point on this triangle closest to `point`
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        // Real-Time Collision Detection 5.1.5
+        let [a, b, c] = self.points;
+        let (ab, ac, ap) = (b - a, c - a, point - a);
+        let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return a;
+        }
+
+        let bp = point - b;
+        let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+        if d3 >= 0.0 && d4 <= d3 {
+            return b;
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        let cp = point - c;
+        let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+        if d6 >= 0.0 && d5 <= d6 {
+            return c;
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        let denom = va + vb + vc;
+        if denom == 0.0 {
+            // degenerate, every edge case above was already ruled out
+            return a;
+        }
+
+        a + ab * (vb / denom) + ac * (vc / denom)
     }
 
     /// Flips this triangles normal  
     /// and reverses it's vertices
     pub fn flip(&self) -> Triangle {
-        Triangle::new(
-            [self[2], self[1], self[0]],
-            [self.uvs[2], self.uvs[1], self.uvs[0]],
-            -self.normal,
-        )
+        Triangle {
+            points: [self[2], self[1], self[0]],
+            uvs: [self.uvs[2], self.uvs[1], self.uvs[0]],
+            normal: -self.normal,
+            ..*self
+        }
     }
 }
 
@@ -92,11 +141,14 @@ impl From<Polygon> for Vec<Triangle> {
             let v1 = &value.vertices[i];
             let v2 = &value.vertices[i + 1];
 
-            out.push(Triangle::new(
-                [v0.point, v1.point, v2.point],
-                [v0.uv, v1.uv, v2.uv],
-                (v0.normal + v1.normal + v2.normal) / 3.0,
-            ));
+            out.push(Triangle {
+                material: value.material,
+                ..Triangle::new(
+                    [v0.point, v1.point, v2.point],
+                    [v0.uv, v1.uv, v2.uv],
+                    (v0.normal + v1.normal + v2.normal) / 3.0,
+                )
+            });
         }
 
         out
@@ -1,7 +1,14 @@
-use crate::plane::{Plane, Side};
+use crate::{
+    attribute::Source,
+    plane::{Plane, Side},
+};
 
 use super::Triangle;
 
+/// A piece of a sliced triangle
+/// and where its corners come from in the whole triangle
+pub(crate) type Sliced = (Triangle, [Source; 3]);
+
 impl Triangle {
     /// Subdivides this triangle into 4 other triangles
     pub fn subdivide(&self) -> [Triangle; 4] {
@@ -12,17 +19,34 @@ impl Triangle {
         let uv12 = self.uvs[1] + (self.uvs[2] - self.uvs[1]) * 0.5;
         let uv20 = self.uvs[2] + (self.uvs[0] - self.uvs[2]) * 0.5;
 
+        let part = |points, uvs| Triangle {
+            points,
+            uvs,
+            ..*self
+        };
+
         [
-            Triangle::new([self[0], p01, p20], [self.uvs[0], uv01, uv20], self.normal),
-            Triangle::new([p01, self[1], p12], [uv01, self.uvs[1], uv12], self.normal),
-            Triangle::new([p20, p12, self[2]], [uv20, uv12, self.uvs[2]], self.normal),
-            Triangle::new([p01, p12, p20], [uv01, uv12, uv20], self.normal),
+            part([self[0], p01, p20], [self.uvs[0], uv01, uv20]),
+            part([p01, self[1], p12], [uv01, self.uvs[1], uv12]),
+            part([p20, p12, self[2]], [uv20, uv12, self.uvs[2]]),
+            part([p01, p12, p20], [uv01, uv12, uv20]),
         ]
     }
 
     /// Slices this triangle in half  
     /// outputs 3-1 triangles total
     pub fn slice(&self, plane: &Plane, above: &mut Vec<Triangle>, below: &mut Vec<Triangle>) {
+        let (a, b) = self.slice_sources(plane);
+        above.extend(a.into_iter().map(|(t, _)| t));
+        below.extend(b.into_iter().map(|(t, _)| t));
+    }
+
+    /// Slices this triangle in half  
+    /// returns the triangles above and below `plane`
+    /// with where their corners come from in this triangle
+    pub(crate) fn slice_sources(&self, plane: &Plane) -> (Vec<Sliced>, Vec<Sliced>) {
+        let mut above = Vec::new();
+        let mut below = Vec::new();
         let d = plane.normal.dot(plane.point);
         let sides = [
             plane.side(self[0]),
@@ -32,15 +56,16 @@ impl Triangle {
 
         // Skip for loop
         if sides[0].aprox_equals(&sides[1]) && sides[0].aprox_equals(&sides[2]) {
+            let sources = [0, 1, 2].map(Source::Copy);
             if sides[0].aprox_above() && sides[1].aprox_above() && sides[2].aprox_above() {
-                above.push(*self);
+                above.push((*self, sources.clone()));
             }
 
             if sides[0].aprox_below() && sides[1].aprox_below() && sides[2].aprox_below() {
-                below.push(*self);
+                below.push((*self, sources));
             }
 
-            return;
+            return (above, below);
         }
 
         let mut vabove = Vec::with_capacity(3);
@@ -50,11 +75,11 @@ impl Triangle {
             let si = sides[i];
 
             if matches!(si, Side::Above | Side::Coplanar) {
-                vabove.push((self[i], self.uvs[i]));
+                vabove.push((self[i], self.uvs[i], Source::Copy(i)));
             }
 
             if matches!(si, Side::Below | Side::Coplanar) {
-                vbelow.push((self[i], self.uvs[i]));
+                vbelow.push((self[i], self.uvs[i], Source::Copy(i)));
             }
 
             if matches!(
@@ -64,38 +89,46 @@ impl Triangle {
                 let vector = self[j] - self[i];
                 let t = (d - plane.normal.dot(self[i])) / plane.normal.dot(vector);
 
-                let v = self[i] + vector * t; // get vertex
+                let v = plane.cross_point(self[i], self[j]); // get vertex
                 let uv = self.uvs[i].lerp(self.uvs[j], t); // get uv
 
-                vabove.push((v, uv));
-                vbelow.push((v, uv));
+                vabove.push((v, uv, Source::Lerp(i, j, t)));
+                vbelow.push((v, uv, Source::Lerp(i, j, t)));
             }
 
             // Add above triangle
             while vabove.len() >= 3 {
-                let v0 = vabove[0];
+                let v0 = vabove[0].clone();
                 let v1 = vabove.remove(1);
-                let v2 = vabove[1];
-
-                above.push(Triangle::new(
-                    [v0.0, v1.0, v2.0],
-                    [v0.1, v1.1, v2.1],
-                    self.normal,
+                let v2 = vabove[1].clone();
+
+                above.push((
+                    Triangle {
+                        points: [v0.0, v1.0, v2.0],
+                        uvs: [v0.1, v1.1, v2.1],
+                        ..*self
+                    },
+                    [v0.2, v1.2, v2.2],
                 ));
             }
 
             // Add below triangle
             while vbelow.len() >= 3 {
-                let v0 = vbelow[0];
+                let v0 = vbelow[0].clone();
                 let v1 = vbelow.remove(1);
-                let v2 = vbelow[1];
-
-                below.push(Triangle::new(
-                    [v0.0, v1.0, v2.0],
-                    [v0.1, v1.1, v2.1],
-                    self.normal,
+                let v2 = vbelow[1].clone();
+
+                below.push((
+                    Triangle {
+                        points: [v0.0, v1.0, v2.0],
+                        uvs: [v0.1, v1.1, v2.1],
+                        ..*self
+                    },
+                    [v0.2, v1.2, v2.2],
                 ));
             }
         }
+
+        (above, below)
     }
 }
@@ -0,0 +1,198 @@
+use glam::Vec2;
+
+/// returns the signed area of a polygon  
+/// positive when it's counter clockwise
+pub(crate) fn signed_area(points: &[Vec2]) -> f32 {
+    let mut sum = 0.0;
+    for i in 0..points.len() {
+        let j = (i + 1) % points.len();
+        sum += points[i].perp_dot(points[j]);
+    }
+
+    sum * 0.5
+}
+
+/// returns whether `point` is inside `polygon` using the even-odd rule
+pub(crate) fn contains(polygon: &[Vec2], point: Vec2) -> bool {
+    let mut inside = false;
+    for i in 0..polygon.len() {
+        let a = polygon[i];
+        let b = polygon[(i + 1) % polygon.len()];
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+        {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/// Triangulates a polygon with holes using ear clipping
+///
+/// holes are bridged into `outer` before clipping,
+/// returns counter clockwise triangles
+/// indexing into `outer` followed by every hole
+pub(crate) fn triangulate(outer: &[Vec2], holes: &[Vec<Vec2>]) -> Vec<[usize; 3]> {
+    let mut points = outer.to_vec();
+    let mut ring: Vec<usize> = (0..outer.len()).collect();
+    if signed_area(outer) < 0.0 {
+        ring.reverse();
+    }
+
+    let mut hole_rings = Vec::new();
+    for hole in holes {
+        let mut hole_ring: Vec<usize> = (points.len()..points.len() + hole.len()).collect();
+        if signed_area(hole) > 0.0 {
+            hole_ring.reverse();
+        }
+
+        points.extend(hole);
+        hole_rings.push(hole_ring);
+    }
+
+    // Bridge the right most hole first so bridges never cross
+    let max_x = |ring: &Vec<usize>| ring.iter().map(|&i| points[i].x).fold(f32::MIN, f32::max);
+    hole_rings.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+    for hole in hole_rings {
+        if hole.len() >= 3 {
+            bridge(&points, &mut ring, &hole);
+        }
+    }
+
+    clip_ears(&points, ring)
+}
+
+/// Connects `hole` to `ring` with a pair of edges
+/// from it's right most vertex to a visible vertex on `ring`
+fn bridge(points: &[Vec2], ring: &mut Vec<usize>, hole: &[usize]) {
+    // https://www.geometrictools.com/Documentation/TriangulationByEarClipping.pdf
+    let (m, &hole_start) = hole
+        .iter()
+        .enumerate()
+        .max_by(|a, b| points[*a.1].x.total_cmp(&points[*b.1].x))
+        .unwrap();
+    let mp = points[hole_start];
+
+    // Cast a ray towards +x and find the closest edge it hits
+    let mut closest: Option<(f32, usize)> = None;
+    for i in 0..ring.len() {
+        let a = points[ring[i]];
+        let b = points[ring[(i + 1) % ring.len()]];
+        if (a.y > mp.y) == (b.y > mp.y) || a.y == b.y {
+            continue;
+        }
+
+        let x = a.x + (mp.y - a.y) / (b.y - a.y) * (b.x - a.x);
+        if x < mp.x || closest.is_some_and(|c| c.0 <= x) {
+            continue;
+        }
+
+        // Use the end point furthest along the ray
+        let j = if a.x > b.x { i } else { (i + 1) % ring.len() };
+        closest = Some((x, j));
+    }
+
+    let (x, mut visible) = match closest {
+        Some(c) => c,
+        None => return,
+    };
+
+    // A reflex vertex inside the triangle (M, I, P) would block P,
+    // use the one closest in angle to the ray instead
+    let hit = Vec2::new(x, mp.y);
+    let p = points[ring[visible]];
+    let mut best = f32::MAX;
+    for i in 0..ring.len() {
+        let r = points[ring[i]];
+        if r == p || !in_triangle(r, mp, hit, p) || is_convex(points, ring, i) {
+            continue;
+        }
+
+        let d = r - mp;
+        let angle = d.y.abs() / d.length();
+        if angle < best {
+            best = angle;
+            visible = i;
+        }
+    }
+
+    let mut bridged = Vec::with_capacity(ring.len() + hole.len() + 2);
+    bridged.extend_from_slice(&ring[..=visible]);
+    bridged.extend(hole[m..].iter().chain(&hole[..=m]));
+    bridged.extend_from_slice(&ring[visible..]);
+    *ring = bridged;
+}
+
+fn clip_ears(points: &[Vec2], mut ring: Vec<usize>) -> Vec<[usize; 3]> {
+    let mut out = Vec::with_capacity(ring.len().saturating_sub(2));
+    while ring.len() > 3 {
+        let n = ring.len();
+        let ear = (0..n).find(|&i| is_ear(points, &ring, i));
+        let i = match ear {
+            Some(i) => i,
+            // Only degenerate polygons have no ears,
+            // drop a collinear vertex or clip any convex vertex
+            None => match (0..n).find(|&i| area(points, &ring, i).abs() <= f32::EPSILON) {
+                Some(i) => {
+                    ring.remove(i);
+                    continue;
+                }
+                None => match (0..n).find(|&i| is_convex(points, &ring, i)) {
+                    Some(i) => i,
+                    None => break,
+                },
+            },
+        };
+
+        out.push([ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]]);
+        ring.remove(i);
+    }
+
+    if ring.len() == 3 && area(points, &ring, 1) > 0.0 {
+        out.push([ring[0], ring[1], ring[2]]);
+    }
+
+    out
+}
+
+fn is_ear(points: &[Vec2], ring: &[usize], i: usize) -> bool {
+    if !is_convex(points, ring, i) {
+        return false;
+    }
+
+    let n = ring.len();
+    let (a, b, c) = (
+        points[ring[(i + n - 1) % n]],
+        points[ring[i]],
+        points[ring[(i + 1) % n]],
+    );
+
+    ring.iter().all(|&j| {
+        let p = points[j];
+        p == a || p == b || p == c || !in_triangle(p, a, b, c)
+    })
+}
+
+/// returns twice the signed area of the triangle at `ring[i]`
+fn area(points: &[Vec2], ring: &[usize], i: usize) -> f32 {
+    let n = ring.len();
+    let a = points[ring[(i + n - 1) % n]];
+    let b = points[ring[i]];
+    let c = points[ring[(i + 1) % n]];
+    (b - a).perp_dot(c - b)
+}
+
+fn is_convex(points: &[Vec2], ring: &[usize], i: usize) -> bool {
+    area(points, ring, i) > 0.0
+}
+
+/// returns whether `p` is inside or on the edge of the triangle
+fn in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let d0 = (b - a).perp_dot(p - a);
+    let d1 = (c - b).perp_dot(p - b);
+    let d2 = (a - c).perp_dot(p - c);
+    let negative = d0 < 0.0 || d1 < 0.0 || d2 < 0.0;
+    let positive = d0 > 0.0 || d1 > 0.0 || d2 > 0.0;
+    !(negative && positive)
+}
@@ -1,6 +1,9 @@
 use glam::{Vec2, Vec3};
 
-#[derive(Clone, Copy, Debug, Default)]
+/// A point with a uv and normal  
+/// uses `#[repr(C)]` so it can be uploaded in a vertex buffer
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
 pub struct Vertex {
     pub point: Vec3,
     pub uv: Vec2,
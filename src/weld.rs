@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use glam::Vec3;
+
+/// Deduplicates points using a spatial hash
+///
+/// with a tolerance of `0.0` only equal points are merged,  
+/// otherwise points are quantized into cells of `tolerance` size
+/// and merged into the first point within `tolerance`
+///
+/// 2D values such as uvs can be welded with `uv.extend(0.0)`
+#[derive(Clone, Debug, Default)]
+pub struct Welder {
+    tolerance: f32,
+    cells: HashMap<[i64; 3], Vec<usize>>,
+    points: Vec<Vec3>,
+}
+
+impl Welder {
+    pub fn new(tolerance: f32) -> Self {
+        Self {
+            tolerance: tolerance.max(0.0),
+            cells: HashMap::new(),
+            points: Vec::new(),
+        }
+    }
+
+    /// Adds `point` unless a point within the tolerance already exists
+    ///
+    /// then returns it's index
+    pub fn insert(&mut self, point: Vec3) -> usize {
+        let key = self.key(point);
+        if self.tolerance == 0.0 {
+            if let Some(&i) = self.cells.get(&key).and_then(|cell| cell.first()) {
+                return i;
+            }
+        } else if let Some(i) = self.find_near(key, point) {
+            return i;
+        }
+
+        let index = self.points.len();
+        self.points.push(point);
+        self.cells.entry(key).or_default().push(index);
+        index
+    }
+
+    /// returns the deduplicated points
+    pub fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+
+    /// returns the deduplicated points
+    pub fn into_points(self) -> Vec<Vec3> {
+        self.points
+    }
+
+    /// returns the amount of unique points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    fn find_near(&self, key: [i64; 3], point: Vec3) -> Option<usize> {
+        let tolerance = self.tolerance * self.tolerance;
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    let cell = match self.cells.get(&[key[0] + x, key[1] + y, key[2] + z]) {
+                        Some(cell) => cell,
+                        None => continue,
+                    };
+
+                    for &i in cell {
+                        if self.points[i].distance_squared(point) <= tolerance {
+                            return Some(i);
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn key(&self, point: Vec3) -> [i64; 3] {
+        if self.tolerance == 0.0 {
+            // `+ 0.0` turns -0.0 into 0.0 so both hash the same
+            let p = point + Vec3::ZERO;
+            [
+                p.x.to_bits() as i64,
+                p.y.to_bits() as i64,
+                p.z.to_bits() as i64,
+            ]
+        } else {
+            let p = (point / self.tolerance).floor();
+            [p.x as i64, p.y as i64, p.z as i64]
+        }
+    }
+}